@@ -36,7 +36,7 @@ fn main() {
     // this with the current glium mutability/rebuild design
     let window = display.get_window().unwrap();
     let target = rovr::target::GlutinRenderTarget::new(&window, 1);
-    let render = hmd.render_to(&target).unwrap();
+    let mut render = hmd.render_to(&target, &rovr::RenderOptions::new()).unwrap();
 
     let program = basic_shader::compile(&display);
     let (vertex_buffer, index_buffer) = basic_shader::cube(&display);
@@ -44,7 +44,7 @@ fn main() {
     let attachments = glium_oculus::Attachments::new(&display, &render);
     let mut surfaces = glium_oculus::Surfaces::new(&display, &attachments);
 
-    display_loop(&display, &attachments, &mut surfaces, |m, surface| {
+    display_loop(&display, &mut render, &attachments, &mut surfaces, |m, surface| {
         use glium::Surface;
         use cgmath::FixedArray;
 
@@ -67,6 +67,7 @@ fn main() {
 
 fn display_loop<'a, F: Fn(&cgmath::Matrix4<f32>, &mut glium::framebuffer::SimpleFrameBuffer)>(
     display: &glium::Display, 
+    render_context: &mut rovr::render::RenderContext,
     attachments: &'a glium_oculus::Attachments,
     surfaces: &'a mut glium_oculus::Surfaces<'a>,
     draw: F) {
@@ -75,13 +76,13 @@ fn display_loop<'a, F: Fn(&cgmath::Matrix4<f32>, &mut glium::framebuffer::Simple
     let mut frame_index = 0u32;
     let (proj_left, proj_right) = {
         let projections: Vec<_> = [rovr::Eye::Left, rovr::Eye::Right].iter().map(|eye| {
-            attachments.get_render_context().projection_matrix(&eye, 0.2f32, 10f32)
+            render_context.projection_matrix(&eye, 0.2f32, 10f32)
         }).collect();
         (projections[0], projections[1])
     };
     loop {
         {
-            let frame = attachments.start_frame();
+            let frame = rovr::render::Frame::new(render_context, attachments.binding()).unwrap();
             for pose in frame.eye_poses() {
                 let projection = match pose.eye {
                     rovr::Eye::Left => proj_left,
@@ -111,17 +112,18 @@ fn display_loop<'a, F: Fn(&cgmath::Matrix4<f32>, &mut glium::framebuffer::Simple
 
                 draw(&eye_transform, surfaces.surface_for_eye(&pose.eye));
             }
+            frame.submit().unwrap();
         }
 
         for event in display.poll_events() {
             match event {
                 glutin::Event::Closed => return,
                 glutin::Event::KeyboardInput(_, _, key) => {
-                    attachments.get_render_context().dismiss_hsw();
+                    render_context.dismiss_hsw();
                     match key {
                         Some(glutin::VirtualKeyCode::Escape) => return,
                         Some(glutin::VirtualKeyCode::R) =>
-                            attachments.get_render_context().recenter_pose(),
+                            render_context.recenter_pose(),
                         _ => {}
                     }
                 },
@@ -138,8 +140,7 @@ mod glium_oculus {
     use glium::texture::{Texture2d, DepthTexture2d};
     use glium::framebuffer::SimpleFrameBuffer;
 
-    pub struct Attachments<'a> {
-        render_context: &'a rovr::render::RenderContext<'a>,
+    pub struct Attachments {
         left: PerEyeAttachments,
         right: PerEyeAttachments,
         binding: rovr::render::TextureBinding,
@@ -150,9 +151,9 @@ mod glium_oculus {
         depth: DepthTexture2d,
     }
 
-    impl<'a> Attachments<'a> {
+    impl Attachments {
         pub fn new(display: &glium::Display, 
-                   render_context: &'a rovr::render::RenderContext) -> Attachments<'a> {
+                   render_context: &rovr::render::RenderContext) -> Attachments {
             use glium::GlObject;
 
             let left = Attachments::create_attachment(display, render_context, rovr::Eye::Left);
@@ -160,19 +161,14 @@ mod glium_oculus {
             let binding = render_context.create_binding(left.color.get_id(), right.color.get_id());
 
             Attachments {
-                render_context: render_context,
                 left: left,
                 right: right,
                 binding: binding,
             }
         }
 
-        pub fn start_frame(&self) -> rovr::render::Frame {
-            rovr::render::Frame::new(self.render_context, &self.binding)
-        }
-
-        pub fn get_render_context(&'a self) -> &'a rovr::render::RenderContext {
-            self.render_context
+        pub fn binding(&self) -> &rovr::render::TextureBinding {
+            &self.binding
         }
 
         fn create_attachment(display: &glium::Display, 
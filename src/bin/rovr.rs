@@ -0,0 +1,373 @@
+//! `rovr` command-line tool. Reports on the installed Oculus runtime and attached headset, and
+//! exercises tracking and rendering to help diagnose setup problems.
+
+extern crate rovr;
+#[cfg(feature = "glutin")] extern crate glutin;
+
+use std::env;
+use std::io::{self, Write};
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+const USAGE: &'static str = "\
+Usage: rovr <command> [options]
+
+Commands:
+    info          Print runtime, headset, capability and display details
+    track         Stream live head poses to stdout
+    recenter      Recenter the tracking origin on the current head pose
+    dismiss-hsw   Dismiss the Health and Safety Warning
+    render-test   Show a test pattern on the headset (Esc to exit, any other key dismisses
+                  the Health and Safety Warning)
+
+Options:
+    --debug       Use a debug headset if no physical headset is found
+    --json        (info) Print details as JSON
+    --rate <hz>   (track) Samples per second, up to 1000; defaults to 10
+    --count <n>   (track) Stop after n samples; defaults to streaming until killed
+";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(|s| s.as_ref()) {
+        Some("info") => info(&args[1..]),
+        Some("track") => track(&args[1..]),
+        Some("recenter") => with_hmd(&args[1..], |hmd| hmd.recenter_pose()),
+        Some("dismiss-hsw") => with_hmd(&args[1..], |hmd| hmd.dismiss_hsw()),
+        Some("render-test") => render_test(&args[1..]),
+        Some("help") | Some("--help") | Some("-h") => {
+            print!("{}", USAGE);
+            Ok(())
+        },
+        _ => Err(USAGE.to_string())
+    };
+
+    if let Err(message) = result {
+        let _ = writeln!(io::stderr(), "{}", message);
+        process::exit(1);
+    }
+}
+
+fn has_flag(args: &[String], flag: &str) -> bool {
+    args.iter().any(|a| a == flag)
+}
+
+fn flag_value(args: &[String], flag: &str) -> Result<Option<u32>, String> {
+    match args.iter().position(|a| a == flag) {
+        Some(i) => match args.get(i + 1).and_then(|v| v.parse().ok()) {
+            Some(v) if v > 0 => Ok(Some(v)),
+            _ => Err(format!("{} requires a positive integer value", flag))
+        },
+        None => Ok(None)
+    }
+}
+
+fn build_hmd(context: &rovr::Context, args: &[String]) -> Result<rovr::Hmd, String> {
+    let mut builder = context.build_hmd();
+    builder.track(&rovr::TrackingOptions::with_all());
+    if has_flag(args, "--debug") {
+        builder.allow_debug();
+    }
+    builder.build().map_err(|e| format!("Unable to open headset: {}", e))
+}
+
+fn with_hmd<F: FnOnce(&rovr::Hmd)>(args: &[String], f: F) -> Result<(), String> {
+    let context = try!(rovr::Context::new().map_err(|e| e.to_string()));
+    let hmd = try!(build_hmd(&context, args));
+    f(&hmd);
+    Ok(())
+}
+
+fn hmd_cap_names(caps: &rovr::HmdCapabilities) -> Vec<(&'static str, bool)> {
+    vec![("present", caps.present),
+         ("available", caps.available),
+         ("captured", caps.captured),
+         ("extend_desktop", caps.extend_desktop),
+         ("display_off", caps.display_off),
+         ("low_persistence", caps.low_persistence),
+         ("dynamic_prediction", caps.dynamic_prediction),
+         ("no_vsync", caps.no_vsync),
+         ("no_mirror_to_window", caps.no_mirror_to_window)]
+}
+
+fn tracking_cap_names(caps: &rovr::TrackingCapabilities) -> Vec<(&'static str, bool)> {
+    vec![("orientation", caps.orientation),
+         ("mag_yaw_correction", caps.mag_yaw_correction),
+         ("position", caps.position)]
+}
+
+fn display_id_string(id: &rovr::HmdDisplayId) -> String {
+    match id {
+        &rovr::HmdDisplayId::Numeric(n) => n.to_string(),
+        &rovr::HmdDisplayId::Name(ref s) => s.clone(),
+        &rovr::HmdDisplayId::Unavailable => "unavailable".to_string()
+    }
+}
+
+fn info(args: &[String]) -> Result<(), String> {
+    let context = try!(rovr::Context::new().map_err(|e| e.to_string()));
+    let hmd = try!(build_hmd(&context, args));
+    let runtime = context.runtime_version();
+    let desc = hmd.descriptor();
+    let display = hmd.get_display();
+
+    if has_flag(args, "--json") {
+        print_info_json(&runtime, &desc, &display);
+    } else {
+        print_info_text(&runtime, &desc, &display);
+    }
+    Ok(())
+}
+
+fn print_info_text(runtime: &str, desc: &rovr::HmdDescriptor, display: &rovr::HmdDisplay) {
+    fn enabled(names: Vec<(&'static str, bool)>) -> String {
+        let on: Vec<_> = names.into_iter().filter(|&(_, v)| v).map(|(n, _)| n).collect();
+        if on.is_empty() { "none".to_string() } else { on.join(" ") }
+    }
+
+    println!("Runtime:            {}", runtime);
+    println!("Headset:");
+    println!("  Type:             {:?}", desc.hmd_type);
+    println!("  Product:          {}", desc.product_name);
+    println!("  Manufacturer:     {}", desc.manufacturer);
+    println!("  Vendor ID:        {:#06x}", desc.vendor_id);
+    println!("  Product ID:       {:#06x}", desc.product_id);
+    println!("  Serial number:    {}", desc.serial_number);
+    println!("  Firmware:         {}.{}", desc.firmware_version.0, desc.firmware_version.1);
+    println!("  Resolution:       {}x{}", desc.resolution.0, desc.resolution.1);
+    println!("  Camera frustum:   h_fov {:.3} rad, v_fov {:.3} rad, z {:.2}m to {:.2}m",
+             desc.camera_frustum.h_fov, desc.camera_frustum.v_fov,
+             desc.camera_frustum.near_z, desc.camera_frustum.far_z);
+    println!("Capabilities:");
+    println!("  Headset:          {}", enabled(hmd_cap_names(&desc.hmd_caps)));
+    println!("  Tracking:         {}", enabled(tracking_cap_names(&desc.tracking_caps)));
+    println!("Display:");
+    println!("  ID:               {}", display_id_string(&display.id));
+    println!("  Mode:             {}",
+             if desc.hmd_caps.extend_desktop { "extend desktop" } else { "direct" });
+    println!("  Position:         {}, {}", display.x, display.y);
+    println!("  Size:             {}x{}", display.width, display.height);
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c)
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_flags(names: Vec<(&'static str, bool)>) -> String {
+    let fields: Vec<_> = names.into_iter()
+        .map(|(n, v)| format!("{}: {}", json_string(n), v))
+        .collect();
+    format!("{{ {} }}", fields.join(", "))
+}
+
+fn print_info_json(runtime: &str, desc: &rovr::HmdDescriptor, display: &rovr::HmdDisplay) {
+    println!("{{");
+    println!("  \"runtime\": {},", json_string(runtime));
+    println!("  \"hmd\": {{");
+    println!("    \"type\": {},", json_string(&format!("{:?}", desc.hmd_type)));
+    println!("    \"product_name\": {},", json_string(&desc.product_name));
+    println!("    \"manufacturer\": {},", json_string(&desc.manufacturer));
+    println!("    \"vendor_id\": {},", desc.vendor_id);
+    println!("    \"product_id\": {},", desc.product_id);
+    println!("    \"serial_number\": {},", json_string(&desc.serial_number));
+    println!("    \"firmware_version\": [{}, {}],",
+             desc.firmware_version.0, desc.firmware_version.1);
+    println!("    \"resolution\": [{}, {}],", desc.resolution.0, desc.resolution.1);
+    println!("    \"camera_frustum\": \
+              {{ \"h_fov\": {}, \"v_fov\": {}, \"near_z\": {}, \"far_z\": {} }}",
+             desc.camera_frustum.h_fov, desc.camera_frustum.v_fov,
+             desc.camera_frustum.near_z, desc.camera_frustum.far_z);
+    println!("  }},");
+    println!("  \"capabilities\": {{");
+    println!("    \"hmd\": {},", json_flags(hmd_cap_names(&desc.hmd_caps)));
+    println!("    \"tracking\": {}", json_flags(tracking_cap_names(&desc.tracking_caps)));
+    println!("  }},");
+    println!("  \"display\": {{");
+    println!("    \"id\": {},", json_string(&display_id_string(&display.id)));
+    println!("    \"direct\": {},", !desc.hmd_caps.extend_desktop);
+    println!("    \"x\": {},", display.x);
+    println!("    \"y\": {},", display.y);
+    println!("    \"width\": {},", display.width);
+    println!("    \"height\": {}", display.height);
+    println!("  }}");
+    println!("}}");
+}
+
+fn track(args: &[String]) -> Result<(), String> {
+    let rate = try!(flag_value(args, "--rate")).unwrap_or(10);
+    if rate > 1000 {
+        return Err("--rate must be at most 1000".to_string());
+    }
+    let count = try!(flag_value(args, "--count"));
+
+    let context = try!(rovr::Context::new().map_err(|e| e.to_string()));
+    let hmd = try!(build_hmd(&context, args));
+    let interval = Duration::new(0, 1_000_000_000 / rate);
+
+    let mut sample = 0u32;
+    while count.map_or(true, |c| sample < c) {
+        let pose = hmd.tracking_state(0f64).head.pose;
+        let ((w, ref q), ref p) = (pose.orientation, pose.position);
+        println!("{:6} orientation {:8.5} {:8.5} {:8.5} {:8.5}  position {:8.4} {:8.4} {:8.4}",
+                 sample, w, q[0], q[1], q[2], p[0], p[1], p[2]);
+        sample += 1;
+        thread::sleep(interval);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "glutin"))]
+fn render_test(_: &[String]) -> Result<(), String> {
+    Err("render-test requires rovr to be built with the `glutin` feature".to_string())
+}
+
+#[cfg(feature = "glutin")]
+fn render_test(args: &[String]) -> Result<(), String> {
+    use rovr::Eye;
+    use rovr::render::Frame;
+
+    let context = try!(rovr::Context::new().map_err(|e| e.to_string()));
+    let hmd = try!(build_hmd(&context, args));
+
+    let builder = match rovr::target::find_glutin_monitor(&hmd.get_display()) {
+        Some(id) => glutin::WindowBuilder::new().with_fullscreen(id),
+        None => {
+            let (w, h) = hmd.resolution();
+            glutin::WindowBuilder::new().with_dimensions(w, h)
+        }
+    };
+    let window = try!(builder
+        .with_title(String::from("rovr render test"))
+        .with_vsync()
+        .build()
+        .map_err(|e| format!("Unable to create window: {:?}", e)));
+    unsafe { window.make_current(); }
+    let gl = try!(gl::Gl::load(&window));
+
+    let target = rovr::target::GlutinRenderTarget::new(&window, 0);
+    let mut render = try!(hmd.render_to(&target, &rovr::RenderOptions::new())
+                             .map_err(|e| e.to_string()));
+    let textures: Vec<u32> = [Eye::Left, Eye::Right].iter().map(|eye| {
+        let (w, h) = render.target_texture_size(eye);
+        gl.create_texture(w, h, &test_pattern(w, h, eye))
+    }).collect();
+    let binding = render.create_binding(textures[0], textures[1]);
+
+    loop {
+        // the pattern is static, so there's nothing to draw; just submit the textures
+        let frame = try!(Frame::new(&mut render, &binding).map_err(|e| e.to_string()));
+        try!(frame.submit().map_err(|e| e.to_string()));
+
+        for event in window.poll_events() {
+            match event {
+                glutin::Event::Closed => return Ok(()),
+                glutin::Event::KeyboardInput(_, _, Some(glutin::VirtualKeyCode::Escape)) =>
+                    return Ok(()),
+                glutin::Event::KeyboardInput(..) => render.dismiss_hsw(),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Build an RGBA test pattern: a grid every 64 pixels, a crosshair at the center, a border in the
+/// eye's colour (red for left, blue for right) and a yellow marker in the top-left corner so
+/// flipped output is obvious.
+#[cfg(feature = "glutin")]
+fn test_pattern(w: u32, h: u32, eye: &rovr::Eye) -> Vec<u8> {
+    let eye_color = match eye {
+        &rovr::Eye::Left => [255u8, 0, 0, 255],
+        &rovr::Eye::Right => [0u8, 0, 255, 255]
+    };
+    let (cx, cy) = (w / 2, h / 2);
+    let mut pixels = Vec::with_capacity((w * h * 4) as usize);
+
+    // GL textures start at the bottom row
+    for row in 0..h {
+        let y = h - 1 - row;
+        for x in 0..w {
+            let color = if x < 8 || y < 8 || x + 8 >= w || y + 8 >= h {
+                eye_color
+            } else if x < 72 && y < 72 {
+                [255, 255, 0, 255]
+            } else if (x as i32 - cx as i32).abs() <= 1 || (y as i32 - cy as i32).abs() <= 1 {
+                [255, 255, 255, 255]
+            } else if x % 64 == 0 || y % 64 == 0 {
+                [128, 128, 128, 255]
+            } else {
+                [16, 16, 16, 255]
+            };
+            pixels.extend(color.iter().cloned());
+        }
+    }
+    pixels
+}
+
+/// Just enough OpenGL to upload the test pattern textures.
+#[cfg(feature = "glutin")]
+mod gl {
+    use glutin;
+    use std::mem;
+
+    const TEXTURE_2D: u32 = 0x0DE1;
+    const TEXTURE_MAG_FILTER: u32 = 0x2800;
+    const TEXTURE_MIN_FILTER: u32 = 0x2801;
+    const LINEAR: i32 = 0x2601;
+    const RGBA: u32 = 0x1908;
+    const UNSIGNED_BYTE: u32 = 0x1401;
+
+    pub struct Gl {
+        gen_textures: extern "system" fn(i32, *mut u32),
+        bind_texture: extern "system" fn(u32, u32),
+        tex_parameteri: extern "system" fn(u32, u32, i32),
+        tex_image_2d: extern "system" fn(u32, i32, i32, i32, i32, i32, u32, u32, *const u8)
+    }
+
+    impl Gl {
+        pub fn load(window: &glutin::Window) -> Result<Gl, String> {
+            macro_rules! load {
+                ($name:expr) => {{
+                    let p = window.get_proc_address($name);
+                    if p.is_null() {
+                        return Err(format!("Unable to load OpenGL function {}", $name));
+                    }
+                    unsafe { mem::transmute(p) }
+                }}
+            }
+
+            Ok(Gl {
+                gen_textures: load!("glGenTextures"),
+                bind_texture: load!("glBindTexture"),
+                tex_parameteri: load!("glTexParameteri"),
+                tex_image_2d: load!("glTexImage2D")
+            })
+        }
+
+        pub fn create_texture(&self, w: u32, h: u32, rgba: &[u8]) -> u32 {
+            let mut id = 0u32;
+            (self.gen_textures)(1, &mut id);
+            (self.bind_texture)(TEXTURE_2D, id);
+            (self.tex_parameteri)(TEXTURE_2D, TEXTURE_MIN_FILTER, LINEAR);
+            (self.tex_parameteri)(TEXTURE_2D, TEXTURE_MAG_FILTER, LINEAR);
+            (self.tex_image_2d)(TEXTURE_2D, 0, RGBA as i32, w as i32, h as i32, 0,
+                                RGBA, UNSIGNED_BYTE, rgba.as_ptr());
+            (self.bind_texture)(TEXTURE_2D, 0);
+            id
+        }
+    }
+}
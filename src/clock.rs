@@ -0,0 +1,137 @@
+//! Access to the Oculus SDK clock.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use shim;
+
+fn duration_seconds(d: Duration) -> f64 {
+    d.as_secs() as f64 + d.subsec_nanos() as f64 * 1e-9
+}
+
+/// `None` if `s` is negative, not finite or too large for a `Duration`.
+fn seconds_duration(s: f64) -> Option<Duration> {
+    if !(s >= 0f64) || s >= u64::max_value() as f64 {
+        return None;
+    }
+    Some(Duration::new(s.trunc() as u64, (s.fract() * 1e9) as u32))
+}
+
+/// A reference point that is the same moment on both clocks.
+#[derive(Clone, Copy, Debug)]
+struct Epoch {
+    instant: Instant,
+    seconds: f64
+}
+
+impl Epoch {
+    fn to_instant(&self, seconds: f64) -> Option<Instant> {
+        let delta = seconds - self.seconds;
+        if delta >= 0f64 {
+            seconds_duration(delta).and_then(|d| self.instant.checked_add(d))
+        } else {
+            seconds_duration(-delta).and_then(|d| self.instant.checked_sub(d))
+        }
+    }
+
+    fn from_instant(&self, instant: Instant) -> f64 {
+        if instant >= self.instant {
+            self.seconds + duration_seconds(instant.duration_since(self.instant))
+        } else {
+            self.seconds - duration_seconds(self.instant.duration_since(instant))
+        }
+    }
+}
+
+/// The clock used by the Oculus SDK for tracking timestamps and pose prediction. SDK times are
+/// expressed in seconds as `f64`, and can be converted to and from `std::time::Instant`.
+///
+/// Conversions are relative to a reference point sampled when the `Clock` is created. Both clocks
+/// are monotonic, so the mapping is stable over the life of the `Clock`.
+pub struct Clock {
+    context: Arc<shim::Context>,
+    epoch: Epoch
+}
+
+impl Clock {
+    #[doc(hidden)]
+    pub fn new(context: Arc<shim::Context>) -> Clock {
+        let epoch = Epoch {
+            instant: Instant::now(),
+            seconds: context.time_seconds()
+        };
+        Clock {
+            context: context,
+            epoch: epoch
+        }
+    }
+
+    /// Current SDK time, in seconds.
+    pub fn now(&self) -> f64 {
+        self.context.time_seconds()
+    }
+
+    /// Convert an SDK time in seconds to an `Instant`. Returns `None` if `seconds` isn't finite, or
+    /// the time can't be represented as an `Instant`, such as a time before the platform's
+    /// `Instant` origin. The SDK uses `0.0` to mean "now", but this converts it literally: to the
+    /// start of the SDK clock, or `None` if that's before the `Instant` origin.
+    pub fn to_instant(&self, seconds: f64) -> Option<Instant> {
+        self.epoch.to_instant(seconds)
+    }
+
+    /// Convert an `Instant` to an SDK time in seconds.
+    pub fn from_instant(&self, instant: Instant) -> f64 {
+        self.epoch.from_instant(instant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64;
+    use std::time::{Duration, Instant};
+
+    use super::Epoch;
+
+    fn epoch() -> Epoch {
+        Epoch {
+            instant: Instant::now(),
+            seconds: 1e6f64
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let epoch = epoch();
+        for &offset in [0f64, 0.25f64, -0.25f64, 12.5f64, -0.0001f64].iter() {
+            let seconds = epoch.seconds + offset;
+            let instant = epoch.to_instant(seconds).unwrap();
+            assert!((epoch.from_instant(instant) - seconds).abs() < 1e-6f64);
+        }
+    }
+
+    #[test]
+    fn instants_map_to_seconds() {
+        let epoch = epoch();
+        let later = epoch.instant + Duration::from_millis(1500);
+        assert!((epoch.from_instant(later) - (epoch.seconds + 1.5f64)).abs() < 1e-6f64);
+        assert_eq!(epoch.to_instant(epoch.seconds), Some(epoch.instant));
+    }
+
+    #[test]
+    fn times_before_instant_origin() {
+        // further in the past than any platform's instant origin
+        let epoch = epoch();
+        assert_eq!(epoch.to_instant(epoch.seconds - 1e19f64), None);
+
+        // the SDK's "now" sentinel may or may not be representable, but mustn't panic
+        let _ = epoch.to_instant(0f64);
+    }
+
+    #[test]
+    fn non_finite_times() {
+        let epoch = epoch();
+        assert_eq!(epoch.to_instant(f64::NAN), None);
+        assert_eq!(epoch.to_instant(f64::INFINITY), None);
+        assert_eq!(epoch.to_instant(f64::NEG_INFINITY), None);
+    }
+}
@@ -121,6 +121,10 @@ impl Default for ovrInitParams {
     }
 }
 
+pub const OVR_KEY_EYE_HEIGHT: &'static [u8] = b"EyeHeight\0";
+pub const OVR_DEFAULT_EYE_HEIGHT: f32 = 1.675;
+pub const OVR_KEY_DK2_LATENCY: &'static [u8] = b"DK2Latency\0";
+
 pub type ovrHmdType = u32;
 pub const ovrHmd_None: ovrHmdType = 0;
 pub const ovrHmd_DK1: ovrHmdType = 3;
@@ -159,6 +163,18 @@ bitflags!(
     }
 );
 
+bitflags!(
+    #[repr(C)]
+    #[derive(Default)]
+    flags ovrStatusBits: u32 {
+        const ovrStatus_OrientationTracked = 0x0001,
+        const ovrStatus_PositionTracked = 0x0002,
+        const ovrStatus_CameraPoseTracked = 0x0004,
+        const ovrStatus_PositionConnected = 0x0020,
+        const ovrStatus_HmdConnected = 0x0080
+    }
+);
+
 bitflags!(
     #[repr(C)]
     #[derive(Default)]
@@ -295,6 +311,40 @@ pub struct ovrEyeRenderDesc {
     pub HmdToEyeViewOffset: ovrVector3f
 }
 
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct ovrPoseStatef {
+    pub ThePose: ovrPosef,
+    pub AngularVelocity: ovrVector3f,
+    pub LinearVelocity: ovrVector3f,
+    pub AngularAcceleration: ovrVector3f,
+    pub LinearAcceleration: ovrVector3f,
+    pub Pad: f32,
+    pub TimeInSeconds: f64
+}
+
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct ovrSensorData {
+    pub Accelerometer: ovrVector3f,
+    pub Gyro: ovrVector3f,
+    pub Magnetometer: ovrVector3f,
+    pub Temperature: f32,
+    pub TimeInSeconds: f32
+}
+
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct ovrTrackingState {
+    pub HeadPose: ovrPoseStatef,
+    pub CameraPose: ovrPosef,
+    pub LeveledCameraPose: ovrPosef,
+    pub RawSensorData: ovrSensorData,
+    pub StatusFlags: ovrStatusBits,
+    pub LastCameraFrameCounter: u32,
+    pub Pad: u32
+}
+
 #[repr(C)]
 #[derive(Clone, Copy)]
 pub struct ovrFrameTiming {
@@ -307,6 +357,45 @@ pub struct ovrFrameTiming {
     pub EyeScanoutSeconds: [f64; 2]
 }
 
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct ovrHSWDisplayState {
+    pub Displayed: ovrBool,
+    pub Pad: [u8; 7],
+    pub StartTime: f64,
+    pub DismissibleTime: f64
+}
+
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct ovrDistortionVertex {
+    pub ScreenPosNDC: ovrVector2f,
+    pub TimeWarpFactor: f32,
+    pub VignetteFactor: f32,
+    pub TanEyeAnglesR: ovrVector2f,
+    pub TanEyeAnglesG: ovrVector2f,
+    pub TanEyeAnglesB: ovrVector2f
+}
+
+#[repr(C)]
+pub struct ovrDistortionMesh {
+    pub pVertexData: *mut ovrDistortionVertex,
+    pub pIndexData: *mut u16,
+    pub VertexCount: u32,
+    pub IndexCount: u32
+}
+
+impl Default for ovrDistortionMesh {
+    fn default() -> ovrDistortionMesh {
+        ovrDistortionMesh {
+            pVertexData: ptr::null_mut(),
+            pIndexData: ptr::null_mut(),
+            VertexCount: 0,
+            IndexCount: 0
+        }
+    }
+}
+
 macro_rules! function_table {
     ( $( fn $func_name:ident( $( $param_name:ident: $param_type:ty ),* ) -> $ret_type:ty ),+ ) => {
         #[allow(non_snake_case)]
@@ -362,14 +451,29 @@ bitflags!(
 function_table!(
     fn ovr_Initialize(params: *const ovrInitParams) -> ovrBool,
     fn ovr_Shutdown() -> (),
+    fn ovr_GetVersionString() -> *const libc::c_char,
+    fn ovr_GetTimeInSeconds() -> f64,
 
     fn ovrHmd_Create(index: i32) -> *mut ovrHmdDesc,
     fn ovrHmd_CreateDebug(the_type: ovrHmdType) -> *mut ovrHmdDesc,
     fn ovrHmd_Destroy(hmd: *mut ovrHmdDesc) -> (),
+    fn ovrHmd_GetLastError(hmd: *mut ovrHmdDesc) -> *const libc::c_char,
 
+    fn ovrHmd_GetEnabledCaps(hmd: *mut ovrHmdDesc) -> ovrHmdCaps,
     fn ovrHmd_SetEnabledCaps(hmd: *mut ovrHmdDesc, hmdCaps: ovrHmdCaps) -> (),
+    fn ovrHmd_GetHSWDisplayState(hmd: *mut ovrHmdDesc,
+                                 hasWarningState: *mut ovrHSWDisplayState) -> (),
     fn ovrHmd_DismissHSWDisplay(hmd: *mut ovrHmdDesc) -> ovrBool,
     fn ovrHmd_RecenterPose(hmd: *mut ovrHmdDesc) -> (),
+    fn ovrHmd_GetFloat(hmd: *mut ovrHmdDesc,
+                       propertyName: *const libc::c_char,
+                       defaultVal: f32) -> f32,
+    fn ovrHmd_GetFloatArray(hmd: *mut ovrHmdDesc,
+                            propertyName: *const libc::c_char,
+                            values: *mut f32,
+                            arraySize: u32) -> u32,
+    fn ovrHmd_GetLatencyTest2DrawColor(hmd: *mut ovrHmdDesc, rgbColorOut: *mut [u8; 3]) -> ovrBool,
+    fn ovrHmd_GetTrackingState(hmd: *mut ovrHmdDesc, absTime: f64) -> ovrTrackingState,
     fn ovrHmd_ConfigureTracking(hmd: *mut ovrHmdDesc, 
                                 supportedTrackingCaps: ovrTrackingCaps, 
                                 requiredTrackingCaps: ovrTrackingCaps) -> ovrBool,
@@ -382,17 +486,36 @@ function_table!(
                              window: *const libc::c_void,
                              destMirrorRect: *const ovrRecti,
                              sourceRenderTargetRect: *const ovrRecti) -> ovrBool,
+    fn ovrHmd_GetRenderDesc(hmd: *mut ovrHmdDesc, eyeType: u32, fov: ovrFovPort) -> ovrEyeRenderDesc,
     fn ovrHmd_GetFovTextureSize(hmd: *mut ovrHmdDesc, 
                                 eye: i32, 
                                 fov: ovrFovPort, 
                                 pixelsPerDisplayPixel: f32) -> ovrSizei,
 
+    fn ovrHmd_CreateDistortionMesh(hmd: *mut ovrHmdDesc,
+                                   eyeType: u32,
+                                   fov: ovrFovPort,
+                                   distortionCaps: ovrDistortionCaps,
+                                   meshData: *mut ovrDistortionMesh) -> ovrBool,
+    fn ovrHmd_DestroyDistortionMesh(meshData: *mut ovrDistortionMesh) -> (),
+    fn ovrHmd_GetRenderScaleAndOffset(fov: ovrFovPort,
+                                      textureSize: ovrSizei,
+                                      renderViewport: ovrRecti,
+                                      uvScaleOffsetOut: *mut [ovrVector2f; 2]) -> (),
+    fn ovrHmd_GetFrameTiming(hmd: *mut ovrHmdDesc, frameIndex: u32) -> ovrFrameTiming,
+    fn ovrHmd_BeginFrameTiming(hmd: *mut ovrHmdDesc, frameIndex: u32) -> ovrFrameTiming,
+    fn ovrHmd_EndFrameTiming(hmd: *mut ovrHmdDesc) -> (),
+    fn ovrHmd_GetEyeTimewarpMatrices(hmd: *mut ovrHmdDesc,
+                                     eye: u32,
+                                     renderPose: ovrPosef,
+                                     twmOut: *mut [ovrMatrix4f; 2]) -> (),
+
     fn ovrHmd_BeginFrame(hmd: *mut ovrHmdDesc, frameIndex: u32) -> ovrFrameTiming,
     fn ovrHmd_GetEyePoses(hmd: *mut ovrHmdDesc, 
                           frameIndex: u32, 
                           hmdToEyeViewOffset: *const [ovrVector3f; 2], 
                           outEyePoses: *mut [ovrPosef; 2], 
-                          outHmdTrackingState: *mut libc::c_void) -> (),
+                          outHmdTrackingState: *mut ovrTrackingState) -> (),
     fn ovrHmd_EndFrame(hmd: *mut ovrHmdDesc, 
                        renderPose: *const [ovrPosef; 2], 
                        eyeTexture: *const [ovrGLTexture; 2]) -> (),
@@ -400,6 +523,10 @@ function_table!(
     fn ovrMatrix4f_Projection(fov: ovrFovPort, 
                               znear: f32, 
                               zfar: f32, 
-                              projectionModFlags: ovrProjectionModifier) -> ovrMatrix4f
+                              projectionModFlags: ovrProjectionModifier) -> ovrMatrix4f,
+    fn ovrMatrix4f_OrthoSubProjection(projection: ovrMatrix4f,
+                                      orthoScale: ovrVector2f,
+                                      orthoDistance: f32,
+                                      hmdToEyeViewOffsetX: f32) -> ovrMatrix4f
 );
 
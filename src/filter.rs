@@ -0,0 +1,375 @@
+//! Opt-in smoothing filters for tracked poses.
+//!
+//! A `PoseFilter` smooths a single stream of poses, with position and orientation each configured
+//! independently through `Smoothing`. Orientation is always filtered with spherical
+//! interpolation, so filtered orientations stay normalized and take the shortest path. Filters are
+//! driven by the timestamps of their input, not wall-clock time, so the same input always produces
+//! the same output.
+//!
+//! Each stream needs its own filter. `EyePoseFilter` keeps one per eye for `render::Frame` poses.
+//!
+//! # Example
+//!
+//! ```
+//! use rovr::filter::{PoseFilter, Smoothing};
+//! use rovr::tracking::Pose;
+//!
+//! let mut filter = PoseFilter::new(Smoothing::Exponential { time_constant: 0.1f32 },
+//!                                  Smoothing::Off);
+//! let start = Pose { orientation: (1f32, [0f32; 3]), position: [0f32; 3] };
+//! let moved = Pose { position: [1f32, 0f32, 0f32], .. start };
+//!
+//! assert_eq!(filter.filter_pose(0f64, &start).position, [0f32; 3]);
+//!
+//! // after one time constant, the filtered position has covered ~63% of the step
+//! let x = filter.filter_pose(0.1f64, &moved).position[0];
+//! assert!((x - 0.632f32).abs() < 0.001f32);
+//! ```
+
+use std::f32::consts::PI;
+
+use Eye;
+use math;
+use shim::{FrameEyePose, Pose, Quaternion, TrackingState, Vector3};
+
+/// Smallest time step used when input timestamps don't advance.
+const MIN_DT: f32 = 1e-6;
+
+/// Smoothing applied to one component of a pose.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Smoothing {
+    /// Pass input through unchanged.
+    Off,
+
+    /// Exponential moving average. After `time_constant` seconds, the output has covered about
+    /// 63% of the distance to a new, steady input.
+    Exponential {
+        time_constant: f32
+    },
+
+    /// The 1€ filter (Casiez et al., 2012): heavy smoothing when still, light smoothing when
+    /// moving quickly. `min_cutoff` (Hz) controls jitter at rest, `beta` how quickly the cutoff
+    /// rises with speed, and `derivative_cutoff` (Hz) the smoothing of the speed estimate.
+    /// Speeds are in meters per second for position and radians per second for orientation.
+    OneEuro {
+        min_cutoff: f32,
+        beta: f32,
+        derivative_cutoff: f32
+    },
+
+    /// A scalar Kalman filter with a constant-value model. `process_noise` is the expected
+    /// variance growth per second from real motion; `measurement_noise` is the variance of the
+    /// sensor noise. Larger `measurement_noise` relative to `process_noise` smooths more.
+    Kalman {
+        process_noise: f32,
+        measurement_noise: f32
+    }
+}
+
+impl Smoothing {
+    /// A 1€ filter with parameters that work well for head tracking.
+    pub fn one_euro() -> Smoothing {
+        Smoothing::OneEuro {
+            min_cutoff: 1f32,
+            beta: 0.5f32,
+            derivative_cutoff: 1f32
+        }
+    }
+}
+
+/// A value that can be smoothed: a position or an orientation.
+trait Channel: Copy {
+    /// Move `t` of the way from `self` to `target`.
+    fn blend(&self, target: &Self, t: f32) -> Self;
+
+    /// Distance between two values, in meters or radians.
+    fn distance(&self, other: &Self) -> f32;
+}
+
+impl Channel for Vector3 {
+    fn blend(&self, target: &Vector3, t: f32) -> Vector3 {
+        math::lerp(self, target, t)
+    }
+
+    fn distance(&self, other: &Vector3) -> f32 {
+        math::length(&[other[0] - self[0], other[1] - self[1], other[2] - self[2]])
+    }
+}
+
+impl Channel for Quaternion {
+    fn blend(&self, target: &Quaternion, t: f32) -> Quaternion {
+        math::quat_slerp(self, target, t)
+    }
+
+    fn distance(&self, other: &Quaternion) -> f32 {
+        math::quat_angle(self, other)
+    }
+}
+
+/// Smoothing factor for a first-order low-pass filter with the given cutoff frequency.
+fn low_pass_alpha(cutoff: f32, dt: f32) -> f32 {
+    let tau = 1f32 / (2f32 * PI * cutoff);
+    1f32 / (1f32 + tau / dt)
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ChannelFilter<T: Channel> {
+    value: Option<T>,
+    speed: f32,
+    variance: f32
+}
+
+impl<T: Channel> ChannelFilter<T> {
+    fn new() -> ChannelFilter<T> {
+        ChannelFilter {
+            value: None,
+            speed: 0f32,
+            variance: 0f32
+        }
+    }
+
+    fn update(&mut self, smoothing: &Smoothing, input: &T, dt: f32) -> T {
+        let previous = match self.value {
+            Some(v) => v,
+            None => {
+                self.value = Some(*input);
+                self.speed = 0f32;
+                self.variance = match *smoothing {
+                    Smoothing::Kalman { measurement_noise, .. } => measurement_noise,
+                    _ => 0f32
+                };
+                return *input;
+            }
+        };
+        let dt = dt.max(MIN_DT);
+
+        let filtered = match *smoothing {
+            Smoothing::Off => *input,
+            Smoothing::Exponential { time_constant } => {
+                let t = if time_constant > 0f32 {
+                    1f32 - (-dt / time_constant).exp()
+                } else {
+                    1f32
+                };
+                previous.blend(input, t)
+            },
+            Smoothing::OneEuro { min_cutoff, beta, derivative_cutoff } => {
+                let raw_speed = previous.distance(input) / dt;
+                self.speed += low_pass_alpha(derivative_cutoff, dt) * (raw_speed - self.speed);
+                let cutoff = min_cutoff + beta * self.speed;
+                previous.blend(input, low_pass_alpha(cutoff, dt))
+            },
+            Smoothing::Kalman { process_noise, measurement_noise } => {
+                self.variance += process_noise * dt;
+                let gain = self.variance / (self.variance + measurement_noise);
+                self.variance *= 1f32 - gain;
+                previous.blend(input, gain)
+            }
+        };
+        self.value = Some(filtered);
+        filtered
+    }
+}
+
+/// Smooths a single stream of poses.
+#[derive(Clone, Copy, Debug)]
+pub struct PoseFilter {
+    position_smoothing: Smoothing,
+    orientation_smoothing: Smoothing,
+    position: ChannelFilter<Vector3>,
+    orientation: ChannelFilter<Quaternion>,
+    last_time: Option<f64>
+}
+
+impl PoseFilter {
+    /// Create a filter with separate smoothing for position and orientation.
+    pub fn new(position: Smoothing, orientation: Smoothing) -> PoseFilter {
+        PoseFilter {
+            position_smoothing: position,
+            orientation_smoothing: orientation,
+            position: ChannelFilter::new(),
+            orientation: ChannelFilter::new(),
+            last_time: None
+        }
+    }
+
+    /// Forget all history. The next pose passes through unchanged.
+    pub fn reset(&mut self) {
+        *self = PoseFilter::new(self.position_smoothing, self.orientation_smoothing);
+    }
+
+    /// Filter a pose sampled at `time`, in seconds. Times should be non-decreasing.
+    ///
+    /// ```
+    /// use rovr::filter::{PoseFilter, Smoothing};
+    /// use rovr::tracking::Pose;
+    ///
+    /// let mut filter = PoseFilter::new(Smoothing::one_euro(), Smoothing::one_euro());
+    /// let pose = Pose { orientation: (1f32, [0f32; 3]), position: [0.1f32, 1.6f32, -0.2f32] };
+    ///
+    /// // a stationary input is passed through untouched
+    /// for i in 0..100 {
+    ///     assert_eq!(filter.filter_pose(i as f64 / 75f64, &pose), pose);
+    /// }
+    /// ```
+    pub fn filter_pose(&mut self, time: f64, pose: &Pose) -> Pose {
+        let dt = match self.last_time {
+            Some(last) => (time - last) as f32,
+            None => 0f32
+        };
+        self.last_time = Some(time);
+        Pose {
+            orientation: self.orientation.update(&self.orientation_smoothing,
+                                                 &pose.orientation,
+                                                 dt),
+            position: self.position.update(&self.position_smoothing, &pose.position, dt)
+        }
+    }
+
+    /// Filter the head pose of a tracking state, using the state's own timestamp. Velocities,
+    /// accelerations and other fields are passed through unchanged.
+    pub fn filter_state(&mut self, state: &TrackingState) -> TrackingState {
+        let mut filtered = *state;
+        filtered.head.pose = self.filter_pose(state.head.time, &state.head.pose);
+        filtered
+    }
+}
+
+/// A pair of `PoseFilter`s, one per eye, for filtering `render::Frame` eye poses.
+///
+/// ```
+/// use rovr::Eye;
+/// use rovr::filter::{EyePoseFilter, Smoothing};
+/// use rovr::render::FrameEyePose;
+///
+/// let mut filter = EyePoseFilter::new(Smoothing::Kalman { process_noise: 1f32,
+///                                                         measurement_noise: 0.01f32 },
+///                                     Smoothing::Off);
+/// let left = FrameEyePose {
+///     eye: Eye::Left,
+///     orientation: (1f32, [0f32; 3]),
+///     position: [0f32; 3],
+///     status: Default::default()
+/// };
+/// let right = FrameEyePose { eye: Eye::Right, position: [5f32, 0f32, 0f32], .. left };
+///
+/// // each eye is filtered independently, so the first pose of each passes through
+/// assert_eq!(filter.filter(0f64, &left).position, [0f32; 3]);
+/// assert_eq!(filter.filter(0f64, &right).position, [5f32, 0f32, 0f32]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct EyePoseFilter {
+    left: PoseFilter,
+    right: PoseFilter
+}
+
+impl EyePoseFilter {
+    pub fn new(position: Smoothing, orientation: Smoothing) -> EyePoseFilter {
+        EyePoseFilter {
+            left: PoseFilter::new(position, orientation),
+            right: PoseFilter::new(position, orientation)
+        }
+    }
+
+    /// Forget all history for both eyes.
+    pub fn reset(&mut self) {
+        self.left.reset();
+        self.right.reset();
+    }
+
+    /// Filter an eye pose from a frame that began at `time`, in seconds. Use `Frame::time()`.
+    pub fn filter(&mut self, time: f64, pose: &FrameEyePose) -> FrameEyePose {
+        let filter = match pose.eye {
+            Eye::Left => &mut self.left,
+            Eye::Right => &mut self.right
+        };
+        let filtered = filter.filter_pose(time, &Pose {
+            orientation: pose.orientation,
+            position: pose.position
+        });
+        FrameEyePose {
+            orientation: filtered.orientation,
+            position: filtered.position,
+            .. *pose
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shim::{Pose, TrackingState};
+    use math;
+    use super::{ChannelFilter, PoseFilter, Smoothing};
+
+    /// Distance the filtered output trails a ramp moving at `speed`, relative to the speed, after
+    /// a second of filtering at 100Hz.
+    fn ramp_lag(smoothing: &Smoothing, speed: f32) -> f32 {
+        let mut filter = ChannelFilter::new();
+        let mut output = [0f32; 3];
+        let mut input = [0f32; 3];
+        for i in 0..101 {
+            input = [speed * i as f32 / 100f32, 0f32, 0f32];
+            output = filter.update(smoothing, &input, 0.01f32);
+        }
+        (input[0] - output[0]) / speed
+    }
+
+    #[test]
+    fn one_euro_cutoff_adapts_to_speed() {
+        let adaptive = Smoothing::one_euro();
+        assert!(ramp_lag(&adaptive, 10f32) < 0.25f32 * ramp_lag(&adaptive, 0.01f32));
+
+        // without beta, the cutoff is fixed and the relative lag doesn't depend on speed
+        let fixed = Smoothing::OneEuro { min_cutoff: 1f32, beta: 0f32, derivative_cutoff: 1f32 };
+        assert!((ramp_lag(&fixed, 10f32) - ramp_lag(&fixed, 0.01f32)).abs() < 1e-3f32);
+    }
+
+    #[test]
+    fn kalman_converges_on_constant_signal() {
+        let smoothing = Smoothing::Kalman { process_noise: 0.001f32, measurement_noise: 0.01f32 };
+        let mut filter = ChannelFilter::new();
+        let mut output = [0f32; 3];
+        for i in 0..500 {
+            // deterministic noise of +-0.1 around 2
+            let noise = if i % 2 == 0 { 0.1f32 } else { -0.1f32 };
+            output = filter.update(&smoothing, &[2f32 + noise, 0f32, 0f32], 0.01f32);
+        }
+        assert!((output[0] - 2f32).abs() < 0.01f32);
+        assert!(filter.variance > 0f32 && filter.variance < 0.01f32);
+    }
+
+    fn state(time: f64, x: f32, yaw: f32) -> TrackingState {
+        let mut state = TrackingState::default();
+        state.head.time = time;
+        state.head.pose = Pose { orientation: math::quat_from_yaw(yaw), position: [x, 0f32, 0f32] };
+        state
+    }
+
+    #[test]
+    fn repeated_and_backwards_timestamps() {
+        let smoothings = [Smoothing::Exponential { time_constant: 0.1f32 },
+                          Smoothing::one_euro(),
+                          Smoothing::Kalman { process_noise: 1f32, measurement_noise: 0.01f32 }];
+        for smoothing in smoothings.iter() {
+            let mut filter = PoseFilter::new(*smoothing, *smoothing);
+            filter.filter_state(&state(1f64, 0f32, 0f32));
+            for &time in [1f64, 0.5f64, 0.5f64].iter() {
+                let pose = filter.filter_state(&state(time, 1f32, 1f32)).head.pose;
+                assert!(pose.position.iter().all(|p| p.is_finite()));
+                let q = pose.orientation;
+                assert!((math::quat_dot(&q, &q).sqrt() - 1f32).abs() < 1e-5f32);
+            }
+        }
+
+        // for the time-based filters, a time that doesn't advance counts as a tiny step, so the
+        // output barely moves; the Kalman gain depends on measurement noise as well as time
+        for smoothing in smoothings[..2].iter() {
+            let mut filter = PoseFilter::new(*smoothing, *smoothing);
+            filter.filter_state(&state(1f64, 0f32, 0f32));
+            let stalled = filter.filter_state(&state(1f64, 1f32, 1f32)).head.pose;
+            assert!(stalled.position[0] < 0.01f32, "{:?} moved on a repeated time", smoothing);
+            assert!(math::quat_angle(&stalled.orientation, &math::IDENTITY) < 0.01f32);
+        }
+    }
+}
@@ -1,6 +1,21 @@
 //! Idiomatic Rust bindings for the Oculus SDK. Provides access to headset
 //! metadata and tracking information, plus helpers for attaching the headset
 //! to an OpenGL rendering context.
+//!
+//! # Serialization
+//!
+//! With the `serde` feature enabled, rovr's plain data types implement `Serialize` and
+//! `Deserialize`. The serialized form follows serde's defaults, and is considered part of rovr's
+//! public API:
+//!
+//! * Structs serialize as maps keyed by their public field names, e.g. `HmdDisplay` becomes
+//!   `{"id": ..., "x": 0, "y": 0, "width": 1920, "height": 1080}`.
+//! * Unit enum variants serialize as their name, e.g. `Eye::Left` becomes `"Left"`. Variants
+//!   with data are wrapped in a single-entry map, e.g. `HmdDisplayId::Numeric(5)` becomes
+//!   `{"Numeric": 5}`.
+//! * The math types in `render` are plain tuples and arrays: a `Vector3` is `[x, y, z]`, a
+//!   `Quaternion` is `[w, [x, y, z]]` and a `Matrix4` is an array of four columns.
+//! * Tuple fields such as `HmdDescriptor::resolution` serialize as arrays.
 
 #[macro_use] extern crate bitflags;
 extern crate libc;
@@ -10,17 +25,35 @@ extern crate libc;
 #[cfg(feature = "glutin")]
 extern crate glutin;
 
-use std::rc::Rc;
+#[cfg(feature = "serde")]
+#[macro_use] extern crate serde;
+
+use std::sync::Arc;
 use std::fmt;
+use std::time::Duration;
 
 mod ffi;
 mod shim;
+mod clock;
+mod math;
 
 pub use shim::HmdDisplayId;
 pub use shim::HmdDisplay;
-
+pub use shim::HmdDescriptor;
+pub use shim::HmdType;
+pub use shim::HmdCapabilities;
+pub use shim::TrackingCapabilities;
+pub use shim::CameraFrustum;
+pub use shim::HswState;
+pub use clock::Clock;
+
+pub mod filter;
+pub mod record;
 pub mod render;
+pub mod service;
+pub mod software;
 pub mod target;
+pub mod tracking;
 
 /// Error produced while interacting with a wrapped Oculus device.
 #[derive(Clone, Debug)]
@@ -36,7 +69,27 @@ pub enum OculusError {
 
     /// Only one `Context` can be active at a time per process. This error occurs when attempting to
     /// create a second `Context` while a `Context` is already active.
-    DuplicateContext
+    DuplicateContext,
+
+    /// A capability required by `TrackingOptions` isn't available on this headset, or couldn't be
+    /// enabled. The included string names the missing capability.
+    MissingTrackingCapability(&'static str),
+
+    /// A distortion option requested by `RenderOptions` isn't supported by this headset. The
+    /// included string names the unsupported option.
+    UnsupportedRenderOption(&'static str),
+
+    /// The SDK reported an error while submitting a frame. The included string is the SDK's own
+    /// description of the error.
+    SubmitError(String),
+
+    /// A frame was begun or ended incorrectly, for example while another frame was open on the
+    /// same `RenderContext`. The included string describes the problem.
+    FrameError(&'static str),
+
+    /// Input that doesn't come from the SDK was malformed, such as an image whose data doesn't
+    /// match its size. The included string describes the problem.
+    InvalidInput(&'static str)
 }
 
 impl fmt::Display for OculusError {
@@ -45,12 +98,22 @@ impl fmt::Display for OculusError {
             &OculusError::OculusRuntimeError(ref description) => f.write_str(description),
             &OculusError::SdkError(ref description) => f.write_str(description),
             &OculusError::DuplicateContext => f.write_str(
-                "Context creation failed because another Context is already active in this process")
+                "Context creation failed because another Context is already active in this process"),
+            &OculusError::MissingTrackingCapability(capability) => write!(f,
+                "Required tracking capability is not available: {}", capability),
+            &OculusError::UnsupportedRenderOption(option) => write!(f,
+                "Render option is not supported by this headset: {}", option),
+            &OculusError::SubmitError(ref description) => write!(f,
+                "Frame submission failed: {}", description),
+            &OculusError::FrameError(description) => f.write_str(description),
+            &OculusError::InvalidInput(description) => f.write_str(description)
         }
     }
 }
 
-#[derive(Copy, Clone)]
+/// One of the headset's two eyes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Eye {
     Left,
     Right
@@ -59,7 +122,7 @@ pub enum Eye {
 /// Oculus SDK context. Ensures the Oculus SDK has been initialized properly, and serves as a
 /// factory for builders that give access to the HMD.
 pub struct Context {
-    shim_context: Rc<shim::Context>
+    shim_context: Arc<shim::Context>
 }
 
 impl Context {
@@ -71,7 +134,7 @@ impl Context {
     /// fail with `Err(OculsuError::DuplicateContext)`. Note that `Hmd`s hold an internal reference
     /// to their associated context.
     pub fn new() -> Result<Context, OculusError> {
-        let shim_context = Rc::new(try!(shim::Context::new()));
+        let shim_context = Arc::new(try!(shim::Context::new()));
         Ok(Context {
             shim_context: shim_context
         })
@@ -81,18 +144,34 @@ impl Context {
     pub fn build_hmd(&self) -> HmdBuilder {
         HmdBuilder::new(self.shim_context.clone())
     }
+
+    /// Access the SDK clock, which serves as the timebase for tracking timestamps and prediction.
+    pub fn clock(&self) -> Clock {
+        Clock::new(self.shim_context.clone())
+    }
+
+    /// Version string of the installed Oculus runtime.
+    pub fn runtime_version(&self) -> String {
+        self.shim_context.version_string()
+    }
 }
 
 /// Options for specifying the enabled tracking capabilities of a headset.
+///
+/// Capabilities enabled with `position`, `orientation` and `mag_yaw_correct` are used if the
+/// headset supports them. Capabilities enabled with the `require_*` variants must be available, or
+/// `HmdBuilder::build` fails with `OculusError::MissingTrackingCapability`.
 pub struct TrackingOptions {
-    track_caps: ffi::ovrTrackingCaps
+    track_caps: ffi::ovrTrackingCaps,
+    required_caps: ffi::ovrTrackingCaps
 }
 
 impl TrackingOptions {
     /// `TrackingOptions` with no tracking options enabled.
     pub fn new() -> TrackingOptions {
         TrackingOptions {
-            track_caps: ffi::ovrTrackingCaps::empty()
+            track_caps: ffi::ovrTrackingCaps::empty(),
+            required_caps: ffi::ovrTrackingCaps::empty()
         }
     }
 
@@ -101,7 +180,8 @@ impl TrackingOptions {
         TrackingOptions {
             track_caps: ffi::ovrTrackingCap_Orientation |
                 ffi::ovrTrackingCap_MagYawCorrection |
-                ffi::ovrTrackingCap_Position
+                ffi::ovrTrackingCap_Position,
+            required_caps: ffi::ovrTrackingCaps::empty()
         }
     }
 
@@ -122,7 +202,225 @@ impl TrackingOptions {
         self.track_caps.insert(ffi::ovrTrackingCap_MagYawCorrection);
         self
     }
-    
+
+    /// Enable and require tracking of head position.
+    pub fn require_position<'f>(&'f mut self) -> &'f mut TrackingOptions {
+        self.required_caps.insert(ffi::ovrTrackingCap_Position);
+        self.position()
+    }
+
+    /// Enable and require tracking of head orientation.
+    pub fn require_orientation<'f>(&'f mut self) -> &'f mut TrackingOptions {
+        self.required_caps.insert(ffi::ovrTrackingCap_Orientation);
+        self.orientation()
+    }
+
+    /// Enable and require yaw drift correction.
+    pub fn require_mag_yaw_correct<'f>(&'f mut self) -> &'f mut TrackingOptions {
+        self.required_caps.insert(ffi::ovrTrackingCap_MagYawCorrection);
+        self.mag_yaw_correct()
+    }
+
+    /// Indicate that the application doesn't currently need tracking, letting the runtime idle
+    /// the tracking hardware. This overrides all other tracking options.
+    pub fn idle<'f>(&'f mut self) -> &'f mut TrackingOptions {
+        self.track_caps.insert(ffi::ovrTrackingCap_Idle);
+        self
+    }
+}
+
+/// Options for rendering to a headset. By default, each eye is rendered at its maximum field of
+/// view with one texture pixel per display pixel at the center of the lens, and the distortion
+/// pass has timewarp and overdrive enabled. With client distortion, the distortion meshes have
+/// timewarp and vignette enabled by default instead; see `Hmd::render_client_distortion`.
+///
+/// In Direct mode, the SDK also mirrors its output to the target window. By default the whole
+/// distorted output fills the window; `mirror_eye` and `mirror_destination` change that, and
+/// `RenderContext::set_mirroring` turns mirroring off and on while rendering. Mirror options are
+/// rejected in Extend mode and with client distortion, where the SDK doesn't mirror.
+///
+/// The SDK only mirrors distorted output, so there is no option for an undistorted mirror. For
+/// one, use client distortion and draw the eye textures to the window alongside the headset
+/// output.
+pub struct RenderOptions {
+    distortion_caps: ffi::ovrDistortionCaps,
+    fovs: [Option<render::FovPort>; 2],
+    pixel_density: f32,
+    mirror_eye: Option<Eye>,
+    mirror_destination: Option<render::Viewport>,
+
+    // false once vignette has been turned off explicitly; client distortion enables it otherwise
+    client_vignette: bool
+}
+
+impl RenderOptions {
+    /// `RenderOptions` with the default options enabled.
+    pub fn new() -> RenderOptions {
+        RenderOptions {
+            distortion_caps: ffi::ovrDistortionCap_TimeWarp | ffi::ovrDistortionCap_Overdrive,
+            fovs: [None, None],
+            pixel_density: 1f32,
+            mirror_eye: None,
+            mirror_destination: None,
+            client_vignette: true
+        }
+    }
+
+    /// Mirror only the given eye's half of the headset output to the target window.
+    pub fn mirror_eye<'f>(&'f mut self, eye: Eye) -> &'f mut RenderOptions {
+        self.mirror_eye = Some(eye);
+        self
+    }
+
+    /// Draw the mirrored output to the given region of the target window, in window pixels from
+    /// the top left, rather than filling the window.
+    pub fn mirror_destination<'f>(&'f mut self,
+                                  destination: render::Viewport) -> &'f mut RenderOptions {
+        self.mirror_destination = Some(destination);
+        self
+    }
+
+    /// Render the given eye with a custom field of view, such as a narrower one to save fill
+    /// rate. See `Hmd::default_fov` and `Hmd::max_fov` for starting points.
+    pub fn fov<'f>(&'f mut self, eye: &Eye, fov: render::FovPort) -> &'f mut RenderOptions {
+        match eye {
+            &Eye::Left => self.fovs[0] = Some(fov),
+            &Eye::Right => self.fovs[1] = Some(fov)
+        }
+        self
+    }
+
+    /// Scale the suggested eye texture size. `density` is the number of texture pixels per display
+    /// pixel at the center of the lens; values below 1 trade sharpness for speed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `density` isn't greater than zero.
+    pub fn pixel_density<'f>(&'f mut self, density: f32) -> &'f mut RenderOptions {
+        assert!(density > 0f32, "pixel density must be greater than zero");
+        self.pixel_density = density;
+        self
+    }
+
+    /// Disable timewarp, which reprojects each frame using the latest head orientation just
+    /// before display.
+    pub fn no_timewarp<'f>(&'f mut self) -> &'f mut RenderOptions {
+        self.distortion_caps.remove(ffi::ovrDistortionCap_TimeWarp);
+        self
+    }
+
+    /// Disable overdrive, which reduces artifacts caused by slow pixel transitions on the
+    /// display.
+    pub fn no_overdrive<'f>(&'f mut self) -> &'f mut RenderOptions {
+        self.distortion_caps.remove(ffi::ovrDistortionCap_Overdrive);
+        self
+    }
+
+    /// Fade out the edges of the image seen through each lens. Always enabled with client
+    /// distortion unless turned off with `no_vignette`.
+    pub fn vignette<'f>(&'f mut self) -> &'f mut RenderOptions {
+        self.distortion_caps.insert(ffi::ovrDistortionCap_Vignette);
+        self.client_vignette = true;
+        self
+    }
+
+    /// Don't fade out the edges of the image seen through each lens, including with client
+    /// distortion.
+    pub fn no_vignette<'f>(&'f mut self) -> &'f mut RenderOptions {
+        self.distortion_caps.remove(ffi::ovrDistortionCap_Vignette);
+        self.client_vignette = false;
+        self
+    }
+
+    /// Treat eye textures as sRGB, converting to linear before distortion.
+    pub fn srgb<'f>(&'f mut self) -> &'f mut RenderOptions {
+        self.distortion_caps.insert(ffi::ovrDistortionCap_SRGB);
+        self
+    }
+
+    /// Use a higher quality, more expensive distortion filter.
+    pub fn hq_distortion<'f>(&'f mut self) -> &'f mut RenderOptions {
+        self.distortion_caps.insert(ffi::ovrDistortionCap_HqDistortion);
+        self
+    }
+
+    /// Flip eye textures vertically before distortion.
+    pub fn flip_input<'f>(&'f mut self) -> &'f mut RenderOptions {
+        self.distortion_caps.insert(ffi::ovrDistortionCap_FlipInput);
+        self
+    }
+
+    /// Run the target window fullscreen on the headset's display. Linux only.
+    pub fn linux_dev_fullscreen<'f>(&'f mut self) -> &'f mut RenderOptions {
+        self.distortion_caps.insert(ffi::ovrDistortionCap_LinuxDevFullscreen);
+        self
+    }
+
+    /// Perform distortion with a compute shader.
+    pub fn compute_shader<'f>(&'f mut self) -> &'f mut RenderOptions {
+        self.distortion_caps.insert(ffi::ovrDistortionCap_ComputeShader);
+        self
+    }
+
+    /// Delay timewarp until just before vsync, trading CPU time for lower latency.
+    pub fn timewarp_jit_delay<'f>(&'f mut self) -> &'f mut RenderOptions {
+        self.distortion_caps.insert(ffi::ovrDistortionCap_TimewarpJitDelay);
+        self
+    }
+
+    /// Don't spin-wait while waiting for timewarp. Useful when profiling.
+    pub fn profile_no_spin_waits<'f>(&'f mut self) -> &'f mut RenderOptions {
+        self.distortion_caps.insert(ffi::ovrDistortionCap_ProfileNoSpinWaits);
+        self
+    }
+
+    /// Don't restore the OpenGL state the SDK modifies while performing distortion. Saves some
+    /// time if the application sets up its own state every frame anyway.
+    pub fn no_restore<'f>(&'f mut self) -> &'f mut RenderOptions {
+        self.distortion_caps.insert(ffi::ovrDistortionCap_NoRestore);
+        self
+    }
+}
+
+/// Options for `RenderContext::projection_matrix_with`. By default, projection matrices are
+/// right-handed and map depth to the OpenGL clip range (-w to w), with the far plane at the far
+/// end of the range.
+pub struct ProjectionOptions {
+    modifiers: ffi::ovrProjectionModifier
+}
+
+impl ProjectionOptions {
+    /// `ProjectionOptions` matching `RenderContext::projection_matrix`.
+    pub fn new() -> ProjectionOptions {
+        ProjectionOptions {
+            modifiers: ffi::ovrProjection_RightHanded | ffi::ovrProjection_ClipRangeOpenGL
+        }
+    }
+
+    /// Produce a left-handed projection, looking down positive z.
+    pub fn left_handed<'f>(&'f mut self) -> &'f mut ProjectionOptions {
+        self.modifiers.remove(ffi::ovrProjection_RightHanded);
+        self
+    }
+
+    /// Map depth to the 0 to w clip range used by Direct3D and Vulkan.
+    pub fn zero_to_one_clip_range<'f>(&'f mut self) -> &'f mut ProjectionOptions {
+        self.modifiers.remove(ffi::ovrProjection_ClipRangeOpenGL);
+        self
+    }
+
+    /// Map the far plane to the near end of the clip range and the near plane to the far end
+    /// ("reversed-Z"), which spreads floating point depth precision more evenly.
+    pub fn reversed_z<'f>(&'f mut self) -> &'f mut ProjectionOptions {
+        self.modifiers.insert(ffi::ovrProjection_FarLessThanNear);
+        self
+    }
+
+    /// Place the far plane at infinity. The `far_z` passed alongside these options is ignored.
+    pub fn infinite_far_plane<'f>(&'f mut self) -> &'f mut ProjectionOptions {
+        self.modifiers.insert(ffi::ovrProjection_FarClipAtInfinity);
+        self
+    }
 }
 
 /// Builder to construct an HMD. Allows the configuration of HMD settings and tracking
@@ -130,16 +428,18 @@ impl TrackingOptions {
 pub struct HmdBuilder {
     caps: ffi::ovrHmdCaps,
     track_caps: ffi::ovrTrackingCaps,
+    required_track_caps: ffi::ovrTrackingCaps,
     allow_debug: bool,
-    owning_context: Rc<shim::Context> 
+    owning_context: Arc<shim::Context> 
 }
 
 impl HmdBuilder {
-    fn new(owning_context: Rc<shim::Context>) -> HmdBuilder {
+    fn new(owning_context: Arc<shim::Context>) -> HmdBuilder {
         let default_caps = ffi::ovrHmdCap_LowPersistence | ffi::ovrHmdCap_DynamicPrediction;
         HmdBuilder { 
             caps: default_caps, 
             track_caps: ffi::ovrTrackingCaps::empty(), 
+            required_track_caps: ffi::ovrTrackingCaps::empty(),
             allow_debug: false,
             owning_context: owning_context
         }
@@ -181,6 +481,7 @@ impl HmdBuilder {
     /// Enable tracking with the specified tracking options.
     pub fn track<'f>(&'f mut self, tracking_options: &TrackingOptions) -> &'f mut HmdBuilder {
         self.track_caps = tracking_options.track_caps;
+        self.required_track_caps = tracking_options.required_caps;
         self
     }
 
@@ -191,8 +492,17 @@ impl HmdBuilder {
     }
 
     /// Build the HMD instance. This will begin tracking if tracking is enabled.
+    ///
+    /// # Failure
+    ///
+    /// Fails with `Err(OculusError::MissingTrackingCapability)` if a tracking capability required
+    /// by the `TrackingOptions` passed to `track` is unavailable.
     pub fn build(&self) -> Result<Hmd, OculusError> {
-        Hmd::new(self.caps, self.track_caps, self.allow_debug, self.owning_context.clone())
+        Hmd::new(self.caps,
+                 self.track_caps,
+                 self.required_track_caps,
+                 self.allow_debug,
+                 self.owning_context.clone())
     }
 }
 
@@ -215,21 +525,58 @@ pub struct Hmd {
 impl Hmd {
     fn new(caps: ffi::ovrHmdCaps, 
            track_caps: ffi::ovrTrackingCaps,
+           required_track_caps: ffi::ovrTrackingCaps,
            allow_debug: bool,
-           owning_context: Rc<shim::Context>) -> Result<Hmd, OculusError> {
+           owning_context: Arc<shim::Context>) -> Result<Hmd, OculusError> {
         let mut shim_hmd = try!(shim::Hmd::new(allow_debug, owning_context));
         shim_hmd.set_caps(caps);
         if !track_caps.is_empty() {
-            try!(shim_hmd.configure_tracking(track_caps));
+            try!(shim_hmd.configure_tracking(track_caps, required_track_caps));
         }
         Ok(Hmd{ shim_hmd: shim_hmd })
     }
 
-    /// Create a `RenderContext` for this headset.
+    /// Default field of view for the given eye, as recommended by the SDK.
+    pub fn default_fov(&self, eye: &Eye) -> render::FovPort {
+        self.shim_hmd.default_fov(eye)
+    }
+
+    /// Largest field of view the given eye can be rendered with. This is the field of view used
+    /// unless `RenderOptions::fov` says otherwise.
+    pub fn max_fov(&self, eye: &Eye) -> render::FovPort {
+        self.shim_hmd.max_fov(eye)
+    }
+
+    /// Create a `RenderContext` for this headset, with the given options.
+    ///
+    /// # Failure
+    ///
+    /// Fails with `Err(OculusError::UnsupportedRenderOption)` if the headset doesn't support one
+    /// of the requested options, or if mirror options are given in Extend mode.
     pub fn render_to<'a>(&'a self,
-                         target: &'a RenderTarget) -> Result<render::RenderContext, OculusError> {
+                         target: &'a RenderTarget,
+                         options: &RenderOptions) -> Result<render::RenderContext, OculusError> {
         use shim::CreateRenderContext;
-        render::RenderContext::new(&self.shim_hmd, target)
+        render::RenderContext::new(&self.shim_hmd, target, options)
+    }
+
+    /// Create a `RenderContext` for this headset that leaves distortion to the application. The
+    /// SDK won't draw to or present the target window; instead, each `Frame` provides the data
+    /// for the application's own distortion pass. See the `render` module documentation for
+    /// details. Of `options`, only timewarp and vignette affect the distortion meshes; the rest
+    /// apply to SDK distortion only. Vignette is enabled unless `RenderOptions::no_vignette` was
+    /// called, so `RenderOptions::new()` gives meshes with timewarp and vignette.
+    ///
+    /// # Failure
+    ///
+    /// Fails with `Err(OculusError::UnsupportedRenderOption)` if the headset doesn't support
+    /// timewarp or vignette when requested, or if any mirror options are given.
+    pub fn render_client_distortion<'a>(&'a self,
+                                        target: &'a RenderTarget,
+                                        options: &RenderOptions)
+            -> Result<render::RenderContext, OculusError> {
+        use shim::CreateRenderContext;
+        render::RenderContext::new_client_distortion(&self.shim_hmd, target, options)
     }
 
     /// Returns a `(width, height)` pair representing the native resolution of the HMD.
@@ -241,5 +588,111 @@ impl Hmd {
     pub fn get_display(&self) -> HmdDisplay {
         self.shim_hmd.get_display()
     }
+
+    /// Return static details about this headset, including its model, serial number and
+    /// capabilities.
+    pub fn descriptor(&self) -> HmdDescriptor {
+        self.shim_hmd.descriptor()
+    }
+
+    /// Query the tracking state of the headset, predicted for `abs_time` (in seconds, on the SDK
+    /// clock). Pass `0.0` for the most recent state without prediction. Unlike `render::Frame`,
+    /// this doesn't require an active `RenderContext` and can be called from any point in a
+    /// program's update loop. While a replay is running, returns the current recorded state; see
+    /// `start_replay`.
+    pub fn tracking_state(&self, abs_time: f64) -> tracking::TrackingState {
+        self.shim_hmd.tracking_state(abs_time)
+    }
+
+    /// Predict the head and eye poses at `abs_time`, in seconds on the SDK clock. Use
+    /// `Context::clock` to convert from an `Instant`. This doesn't require an active
+    /// `RenderContext`.
+    pub fn predict_pose(&self, abs_time: f64) -> tracking::PredictedPose {
+        self.shim_hmd.predict_pose(abs_time)
+    }
+
+    /// Stream raw IMU samples from the headset. The iterator blocks until each new sample is
+    /// available, and ends if no new sample arrives within `timeout`. The stream is lossy; see
+    /// `tracking::SensorSamples`. The latest sample is also available from `tracking_state`.
+    pub fn sensor_samples(&self, timeout: Duration) -> tracking::SensorSamples {
+        tracking::SensorSamples::new(&self.shim_hmd, timeout)
+    }
+
+    /// Recenter the headset, using the current orientation and position as the origin. Any yaw
+    /// correction from `recenter_yaw` is cleared, since the new origin already faces the way the
+    /// head does; the rest of the tracking space is kept.
+    pub fn recenter_pose(&self) {
+        self.shim_hmd.recenter_pose();
+    }
+
+    /// Rotate the tracking space so the current head orientation faces forward, without moving
+    /// the origin. See `tracking::TrackingSpace::recenter_yaw`.
+    pub fn recenter_yaw(&self) {
+        self.shim_hmd.recenter_yaw();
+    }
+
+    /// The tracking space in which this headset reports poses, from both `tracking_state` and
+    /// `render::Frame`.
+    pub fn tracking_space(&self) -> tracking::TrackingSpace {
+        self.shim_hmd.tracking_space()
+    }
+
+    /// Set the tracking space in which this headset reports poses.
+    ///
+    /// ```no_run
+    /// # use rovr::Context;
+    /// # let context = Context::new().unwrap();
+    /// # let hmd = context.build_hmd().build().unwrap();
+    /// let mut space = hmd.tracking_space();
+    /// space.floor_level(hmd.profile_eye_height());
+    /// hmd.set_tracking_space(space);
+    /// ```
+    pub fn set_tracking_space(&self, space: tracking::TrackingSpace) {
+        self.shim_hmd.set_tracking_space(space);
+    }
+
+    /// Read poses from a recording rather than from the headset, until `stop_replay` is called.
+    /// Every pose query on this headset reads from `replay`: `tracking_state`, `predict_pose`,
+    /// `render::Frame` and any `service::TrackingService` polling it. Stepped replays advance one
+    /// recorded frame each time a frame begins. Replaces any replay already running.
+    ///
+    /// ```no_run
+    /// # use rovr::Context;
+    /// use rovr::record::{Playback, Recording, Replay};
+    ///
+    /// # let context = Context::new().unwrap();
+    /// # let hmd = context.build_hmd().allow_debug().build().unwrap();
+    /// let mut file = std::fs::File::open("session.rvrc").unwrap();
+    /// let recording = Recording::read(&mut file).unwrap();
+    /// hmd.start_replay(Replay::new(recording, Playback::RealTime));
+    /// let state = hmd.tracking_state(0.0); // recorded, not live
+    /// ```
+    pub fn start_replay(&self, replay: record::Replay) {
+        self.shim_hmd.start_replay(replay);
+    }
+
+    /// Return to live tracking. Returns the replay that was running, if any.
+    pub fn stop_replay(&self) -> Option<record::Replay> {
+        self.shim_hmd.stop_replay()
+    }
+
+    /// Eye height from the user's Oculus profile, in meters, or the SDK's default if no profile is
+    /// configured. Use with `TrackingSpace::floor_level`.
+    pub fn profile_eye_height(&self) -> f32 {
+        self.shim_hmd.profile_eye_height()
+    }
+
+    /// Dismiss the Health and Safety warning automatically displayed by the Oculus runtime. This
+    /// should only be dismissed in response to user input; see the Oculus SDK documentation for
+    /// details on proper usage.
+    pub fn dismiss_hsw(&self) {
+        self.shim_hmd.dismiss_hsw();
+    }
+
+    /// Current state of the Health and Safety warning: whether it's displayed, and when it can be
+    /// dismissed. Compare `dismissible_time` against `Context::clock().now()`.
+    pub fn hsw_state(&self) -> HswState {
+        self.shim_hmd.hsw_state()
+    }
 }
 
@@ -0,0 +1,141 @@
+//! Small set of vector and quaternion helpers over rovr's tuple-based math types.
+
+use shim::{Quaternion, Vector3};
+
+pub const IDENTITY: Quaternion = (1f32, [0f32, 0f32, 0f32]);
+
+pub fn add(a: &Vector3, b: &Vector3) -> Vector3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+pub fn scale(v: &Vector3, s: f32) -> Vector3 {
+    [v[0] * s, v[1] * s, v[2] * s]
+}
+
+pub fn dot(a: &Vector3, b: &Vector3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub fn cross(a: &Vector3, b: &Vector3) -> Vector3 {
+    [a[1] * b[2] - a[2] * b[1],
+     a[2] * b[0] - a[0] * b[2],
+     a[0] * b[1] - a[1] * b[0]]
+}
+
+/// Hamilton product `a * b`; the result applies `b`, then `a`.
+pub fn quat_mul(a: &Quaternion, b: &Quaternion) -> Quaternion {
+    let (aw, ref av) = *a;
+    let (bw, ref bv) = *b;
+    (aw * bw - dot(av, bv),
+     add(&add(&scale(bv, aw), &scale(av, bw)), &cross(av, bv)))
+}
+
+/// Rotate `v` by the unit quaternion `q`.
+pub fn quat_rotate(q: &Quaternion, v: &Vector3) -> Vector3 {
+    let (w, ref u) = *q;
+    let t = scale(&cross(u, v), 2f32);
+    add(&add(v, &scale(&t, w)), &cross(u, &t))
+}
+
+/// Rotation of `angle` radians about the vertical (y) axis.
+pub fn quat_from_yaw(angle: f32) -> Quaternion {
+    let half = angle * 0.5f32;
+    (half.cos(), [0f32, half.sin(), 0f32])
+}
+
+/// Heading of `q` about the vertical (y) axis, in radians, ignoring pitch and roll. Forward is -z.
+pub fn quat_yaw(q: &Quaternion) -> f32 {
+    let forward = quat_rotate(q, &[0f32, 0f32, -1f32]);
+    (-forward[0]).atan2(-forward[2])
+}
+
+pub fn length(v: &Vector3) -> f32 {
+    dot(v, v).sqrt()
+}
+
+pub fn lerp(a: &Vector3, b: &Vector3, t: f32) -> Vector3 {
+    [a[0] + (b[0] - a[0]) * t,
+     a[1] + (b[1] - a[1]) * t,
+     a[2] + (b[2] - a[2]) * t]
+}
+
+pub fn quat_dot(a: &Quaternion, b: &Quaternion) -> f32 {
+    a.0 * b.0 + dot(&a.1, &b.1)
+}
+
+pub fn quat_normalize(q: &Quaternion) -> Quaternion {
+    let len = quat_dot(q, q).sqrt();
+    if len == 0f32 {
+        IDENTITY
+    } else {
+        (q.0 / len, scale(&q.1, 1f32 / len))
+    }
+}
+
+/// Angle of the rotation between unit quaternions `a` and `b`, in radians.
+pub fn quat_angle(a: &Quaternion, b: &Quaternion) -> f32 {
+    let d = quat_dot(a, b).abs().min(1f32);
+    2f32 * d.acos()
+}
+
+/// Spherical linear interpolation between unit quaternions, along the shortest arc.
+pub fn quat_slerp(a: &Quaternion, b: &Quaternion, t: f32) -> Quaternion {
+    let mut d = quat_dot(a, b);
+    let b = if d < 0f32 {
+        d = -d;
+        (-b.0, scale(&b.1, -1f32))
+    } else {
+        *b
+    };
+
+    // fall back to normalized lerp when the quaternions are nearly parallel
+    let (wa, wb) = if d > 0.9995f32 {
+        (1f32 - t, t)
+    } else {
+        let theta = d.acos();
+        let sin_theta = theta.sin();
+        (((1f32 - t) * theta).sin() / sin_theta, (t * theta).sin() / sin_theta)
+    };
+    quat_normalize(&(a.0 * wa + b.0 * wb, add(&scale(&a.1, wa), &scale(&b.1, wb))))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+
+    fn assert_unit(q: &Quaternion) {
+        assert!((quat_dot(q, q).sqrt() - 1f32).abs() < 1e-5f32, "{:?} is not normalized", q);
+    }
+
+    #[test]
+    fn slerp_takes_shortest_path() {
+        // the same 170 degree rotation, expressed in the opposite hemisphere
+        let far = quat_from_yaw(170f32 * PI / 180f32);
+        let negated = (-far.0, scale(&far.1, -1f32));
+
+        let halfway = quat_slerp(&IDENTITY, &negated, 0.5f32);
+        assert!(quat_angle(&halfway, &quat_from_yaw(85f32 * PI / 180f32)) < 1e-3f32);
+        assert!(quat_angle(&quat_slerp(&IDENTITY, &negated, 1f32), &far) < 1e-3f32);
+    }
+
+    #[test]
+    fn slerp_is_normalized() {
+        let targets = [quat_from_yaw(0.0001f32), quat_from_yaw(1f32), quat_from_yaw(-3f32)];
+        for target in targets.iter() {
+            for i in 0..11 {
+                assert_unit(&quat_slerp(&IDENTITY, target, i as f32 / 10f32));
+            }
+        }
+    }
+
+    #[test]
+    fn slerp_end_points() {
+        let a = quat_from_yaw(0.5f32);
+        let b = quat_from_yaw(2f32);
+        assert!(quat_angle(&quat_slerp(&a, &b, 0f32), &a) < 1e-3f32);
+        assert!(quat_angle(&quat_slerp(&a, &b, 1f32), &b) < 1e-3f32);
+        assert!(quat_angle(&quat_slerp(&a, &b, 0.5f32), &quat_from_yaw(1.25f32)) < 1e-3f32);
+    }
+}
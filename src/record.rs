@@ -0,0 +1,677 @@
+//! Recording and deterministic replay of tracking data.
+//!
+//! A `Recorder` writes frame poses and tracking states to any `Write` implementation, and a
+//! `Recording` reads them back. Frames can be recorded from a rendered `Frame`, or from
+//! `Hmd::predict_pose` without a render context.
+//!
+//! A `Replay` built from a `Recording` can be attached to an `Hmd` with `start_replay`, after
+//! which every pose query on that headset reads from the recording instead of live tracking:
+//! `Frame::eye_poses`, `Hmd::tracking_state`, `Hmd::predict_pose`, and any `TrackingService`
+//! polling it. Replays can run at the original speed or advance one recorded frame per rendered
+//! frame. A debug headset (see `HmdBuilder::allow_debug`) is sufficient for replay, so recordings
+//! can be reproduced on machines without a headset.
+//!
+//! # File format
+//!
+//! All values are little-endian. A file starts with a header:
+//!
+//! | Bytes | Contents                                    |
+//! |-------|---------------------------------------------|
+//! | 4     | Magic bytes `RVRC`                          |
+//! | 2     | Format version, currently 2 (`u16`)         |
+//!
+//! The header is followed by any number of records, each starting with a `u8` record type:
+//!
+//! * `1`, frame: `f64` SDK time, a tracking state, a `u8` eye pose count, then for each eye pose
+//!   a `u8` eye (0 = left, 1 = right), orientation as `f32` w, x, y, z, position as `f32` x, y,
+//!   z, and a `u8` of the eye pose's status bits.
+//! * `2`, tracking state: `f64` SDK time, then a tracking state.
+//!
+//! A tracking state is the head pose (orientation w, x, y, z and position x, y, z), angular
+//! velocity, linear velocity, angular acceleration and linear acceleration (each x, y, z), all as
+//! `f32`; the head pose time as `f64`; the camera pose and leveled camera pose as `f32`; raw
+//! sensor accelerometer, gyro and magnetometer (each x, y, z) and temperature as `f32`; the
+//! sensor time as `f64`; and a `u8` of status bits.
+//!
+//! Status bits are orientation tracked (`0x01`), position tracked (`0x02`), camera pose tracked
+//! (`0x04`), position sensor connected (`0x08`) and HMD connected (`0x10`).
+//!
+//! # Example
+//!
+//! ```
+//! use rovr::record::{Recorder, Recording};
+//! use rovr::tracking::TrackingState;
+//!
+//! let mut recorder = Recorder::new(Vec::new()).unwrap();
+//! recorder.record_tracking_state(1.5f64, &TrackingState::default()).unwrap();
+//! let bytes = recorder.finish().unwrap();
+//!
+//! let recording = Recording::read(&mut &bytes[..]).unwrap();
+//! assert_eq!(recording.tracking_states().len(), 1);
+//! assert_eq!(recording.tracking_states()[0].time, 1.5f64);
+//! ```
+
+use std::cmp;
+use std::io::{self, Read, Write};
+use std::mem;
+use std::time::Instant;
+
+use Eye;
+use shim::{Frame, FrameEyePose, Pose, PoseState, PredictedPose, SensorData, TrackingState};
+use shim::{Quaternion, TrackingStatus, Vector3};
+
+const MAGIC: &'static [u8; 4] = b"RVRC";
+const VERSION: u16 = 2;
+
+/// Size of a tracking state in the recording format, in bytes.
+#[doc(hidden)]
+pub const TRACKING_STATE_BYTES: usize = 189;
+
+const RECORD_FRAME: u8 = 1;
+const RECORD_TRACKING_STATE: u8 = 2;
+
+/// A recorded frame: the time it began, the tracking state used for it, and its eye poses in
+/// rendering order.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecordedFrame {
+    /// Time the frame began, in seconds on the SDK clock.
+    pub time: f64,
+    pub state: TrackingState,
+    pub eye_poses: Vec<FrameEyePose>
+}
+
+/// A tracking state recorded outside of a frame.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecordedTrackingState {
+    /// Time the state was queried, in seconds on the SDK clock.
+    pub time: f64,
+    pub state: TrackingState
+}
+
+/// Writes frame poses and tracking states in the rovr recording format.
+pub struct Recorder<W: Write> {
+    writer: W
+}
+
+impl<W: Write> Recorder<W> {
+    /// Create a recorder, writing the file header to `writer` immediately.
+    pub fn new(mut writer: W) -> io::Result<Recorder<W>> {
+        try!(writer.write_all(MAGIC));
+        try!(write_u16(&mut writer, VERSION));
+        Ok(Recorder { writer: writer })
+    }
+
+    /// Record the poses and tracking state of a frame.
+    pub fn record_frame(&mut self, frame: &Frame) -> io::Result<()> {
+        let poses: Vec<_> = frame.eye_poses().collect();
+        self.record_poses(frame.time(), &frame.tracking_state(), &poses)
+    }
+
+    /// Record a pose predicted with `Hmd::predict_pose` as a frame at `time`, in seconds on the SDK
+    /// clock. Unlike `record_frame`, this doesn't need a render context.
+    pub fn record_predicted_pose(&mut self, time: f64, pose: &PredictedPose) -> io::Result<()> {
+        self.record_poses(time, &pose.state, &pose.eye_poses)
+    }
+
+    /// Record a frame from its parts. `record_frame` is usually more convenient.
+    pub fn record_poses(&mut self,
+                        time: f64,
+                        state: &TrackingState,
+                        eye_poses: &[FrameEyePose]) -> io::Result<()> {
+        let w = &mut self.writer;
+        try!(w.write_all(&[RECORD_FRAME]));
+        try!(write_f64(w, time));
+        try!(write_tracking_state(w, state));
+        try!(w.write_all(&[eye_poses.len() as u8]));
+        for pose in eye_poses {
+            let eye = match pose.eye {
+                Eye::Left => 0u8,
+                Eye::Right => 1u8
+            };
+            try!(w.write_all(&[eye]));
+            try!(write_quaternion(w, &pose.orientation));
+            try!(write_vector3(w, &pose.position));
+            try!(w.write_all(&[status_bits(&pose.status)]));
+        }
+        Ok(())
+    }
+
+    /// Record a tracking state queried at `time`, in seconds on the SDK clock.
+    pub fn record_tracking_state(&mut self, time: f64, state: &TrackingState) -> io::Result<()> {
+        let w = &mut self.writer;
+        try!(w.write_all(&[RECORD_TRACKING_STATE]));
+        try!(write_f64(w, time));
+        write_tracking_state(w, state)
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        try!(self.writer.flush());
+        Ok(self.writer)
+    }
+}
+
+/// A single record from a recording.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Record {
+    Frame(RecordedFrame),
+    TrackingState(RecordedTrackingState)
+}
+
+/// A recording read back into memory.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Recording {
+    /// All records, in the order they were recorded.
+    pub records: Vec<Record>
+}
+
+impl Recording {
+    /// Recorded frames, in the order they were recorded.
+    pub fn frames(&self) -> Vec<&RecordedFrame> {
+        self.records.iter().filter_map(|record| match record {
+            &Record::Frame(ref frame) => Some(frame),
+            _ => None
+        }).collect()
+    }
+
+    /// Tracking states recorded outside of frames, in the order they were recorded.
+    pub fn tracking_states(&self) -> Vec<&RecordedTrackingState> {
+        self.records.iter().filter_map(|record| match record {
+            &Record::TrackingState(ref state) => Some(state),
+            _ => None
+        }).collect()
+    }
+
+    /// Read a complete recording. Fails with `InvalidData` if the header is missing or the
+    /// format version is unsupported, and with `UnexpectedEof` if the input ends partway through
+    /// a record.
+    pub fn read<R: Read>(reader: &mut R) -> io::Result<Recording> {
+        let mut magic = [0u8; 4];
+        try!(reader.read_exact(&mut magic));
+        if &magic != MAGIC {
+            return Err(invalid_data("not a rovr recording"));
+        }
+        if try!(read_u16(reader)) != VERSION {
+            return Err(invalid_data("unsupported rovr recording version"));
+        }
+
+        let mut recording = Recording::default();
+        loop {
+            // end of input is only valid between records
+            let mut kind = [0u8; 1];
+            match reader.read_exact(&mut kind) {
+                Ok(()) => {},
+                Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(recording),
+                Err(e) => return Err(e)
+            }
+            match kind[0] {
+                RECORD_FRAME => {
+                    let time = try!(read_f64(reader));
+                    let state = try!(read_tracking_state(reader));
+                    let count = try!(read_u8(reader));
+                    let mut eye_poses = Vec::with_capacity(count as usize);
+                    for _ in 0..count {
+                        let eye = match try!(read_u8(reader)) {
+                            0 => Eye::Left,
+                            1 => Eye::Right,
+                            _ => return Err(invalid_data("invalid eye in rovr recording"))
+                        };
+                        let orientation = try!(read_quaternion(reader));
+                        let position = try!(read_vector3(reader));
+                        eye_poses.push(FrameEyePose {
+                            eye: eye,
+                            orientation: orientation,
+                            position: position,
+                            status: status_from_bits(try!(read_u8(reader)))
+                        });
+                    }
+                    recording.records.push(Record::Frame(RecordedFrame {
+                        time: time,
+                        state: state,
+                        eye_poses: eye_poses
+                    }));
+                },
+                RECORD_TRACKING_STATE => {
+                    let time = try!(read_f64(reader));
+                    recording.records.push(Record::TrackingState(RecordedTrackingState {
+                        time: time,
+                        state: try!(read_tracking_state(reader))
+                    }));
+                },
+                _ => return Err(invalid_data("unknown record type in rovr recording"))
+            }
+        }
+    }
+}
+
+/// How a `Replay` advances through its recorded frames.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Playback {
+    /// Advance according to elapsed wall-clock time, reproducing the original timing.
+    RealTime,
+
+    /// Advance exactly one recorded frame per rendered frame.
+    Stepped
+}
+
+/// Recorded frames being played back. Once the last frame is reached, it is repeated.
+pub struct Replay {
+    frames: Vec<RecordedFrame>,
+    playback: Playback,
+    position: Option<usize>,
+    started: Option<Instant>
+}
+
+impl Replay {
+    pub fn new(recording: Recording, playback: Playback) -> Replay {
+        let frames = recording.records.into_iter().filter_map(|record| match record {
+            Record::Frame(frame) => Some(frame),
+            _ => None
+        }).collect();
+        Replay {
+            frames: frames,
+            playback: playback,
+            position: None,
+            started: None
+        }
+    }
+
+    /// Index of the recorded frame most recently played back, or `None` before playback starts.
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+
+    /// True once the last recorded frame has been played back.
+    pub fn is_finished(&self) -> bool {
+        match self.position {
+            Some(p) => p + 1 >= self.frames.len(),
+            None => self.frames.is_empty()
+        }
+    }
+
+    /// Advance playback and return the recorded frame to use for the next rendered frame, or
+    /// `None` if the recording contains no frames.
+    pub fn next_frame(&mut self) -> Option<&RecordedFrame> {
+        match self.upcoming() {
+            Some(next) => {
+                self.advance_to(next);
+                Some(&self.frames[next])
+            },
+            None => None
+        }
+    }
+
+    /// The recorded frame being played back, or `None` if the recording contains no frames.
+    /// Stepped playback stays on the frame most recently returned by `next_frame`, or the first
+    /// frame before playback starts; real-time playback follows its clock.
+    pub fn current_frame(&mut self) -> Option<&RecordedFrame> {
+        match self.playback {
+            Playback::Stepped => self.frames.get(self.position.unwrap_or(0)),
+            Playback::RealTime => self.next_frame()
+        }
+    }
+
+    /// Index of the recorded frame that `next_frame` would return, without advancing playback.
+    #[doc(hidden)]
+    pub fn upcoming(&self) -> Option<usize> {
+        if self.frames.is_empty() {
+            return None;
+        }
+        let last = self.frames.len() - 1;
+        Some(match (self.position, self.playback, self.started) {
+            (Some(p), Playback::Stepped, _) => cmp::min(p + 1, last),
+            (Some(p), Playback::RealTime, Some(started)) => {
+                let elapsed = started.elapsed();
+                let target = self.frames[0].time +
+                    elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 * 1e-9;
+                let mut p = p;
+                while p < last && self.frames[p + 1].time <= target {
+                    p += 1;
+                }
+                p
+            },
+            _ => 0
+        })
+    }
+
+    /// Move playback to the recorded frame at `position`, as returned by `upcoming`. The first
+    /// move starts the clock for real-time playback.
+    #[doc(hidden)]
+    pub fn advance_to(&mut self, position: usize) {
+        if position >= self.frames.len() {
+            return;
+        }
+        if self.started.is_none() {
+            self.started = Some(Instant::now());
+        }
+        self.position = Some(position);
+    }
+
+    /// The recorded frame at `position`.
+    #[doc(hidden)]
+    pub fn frame(&self, position: usize) -> Option<&RecordedFrame> {
+        self.frames.get(position)
+    }
+}
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn write_u16<W: Write>(w: &mut W, v: u16) -> io::Result<()> {
+    w.write_all(&[v as u8, (v >> 8) as u8])
+}
+
+fn write_u32<W: Write>(w: &mut W, v: u32) -> io::Result<()> {
+    w.write_all(&[v as u8, (v >> 8) as u8, (v >> 16) as u8, (v >> 24) as u8])
+}
+
+fn write_f32<W: Write>(w: &mut W, v: f32) -> io::Result<()> {
+    write_u32(w, unsafe { mem::transmute::<f32, u32>(v) })
+}
+
+fn write_f64<W: Write>(w: &mut W, v: f64) -> io::Result<()> {
+    let bits = unsafe { mem::transmute::<f64, u64>(v) };
+    try!(write_u32(w, bits as u32));
+    write_u32(w, (bits >> 32) as u32)
+}
+
+fn write_vector3<W: Write>(w: &mut W, v: &Vector3) -> io::Result<()> {
+    for c in v.iter() {
+        try!(write_f32(w, *c));
+    }
+    Ok(())
+}
+
+fn write_quaternion<W: Write>(w: &mut W, q: &Quaternion) -> io::Result<()> {
+    try!(write_f32(w, q.0));
+    write_vector3(w, &q.1)
+}
+
+fn write_pose<W: Write>(w: &mut W, pose: &Pose) -> io::Result<()> {
+    try!(write_quaternion(w, &pose.orientation));
+    write_vector3(w, &pose.position)
+}
+
+/// Write a tracking state in the recording format.
+#[doc(hidden)]
+pub fn write_tracking_state<W: Write>(w: &mut W, state: &TrackingState) -> io::Result<()> {
+    try!(write_pose(w, &state.head.pose));
+    try!(write_vector3(w, &state.head.angular_velocity));
+    try!(write_vector3(w, &state.head.linear_velocity));
+    try!(write_vector3(w, &state.head.angular_acceleration));
+    try!(write_vector3(w, &state.head.linear_acceleration));
+    try!(write_f64(w, state.head.time));
+    try!(write_pose(w, &state.camera_pose));
+    try!(write_pose(w, &state.leveled_camera_pose));
+    try!(write_vector3(w, &state.raw_sensor.accelerometer));
+    try!(write_vector3(w, &state.raw_sensor.gyro));
+    try!(write_vector3(w, &state.raw_sensor.magnetometer));
+    try!(write_f32(w, state.raw_sensor.temperature));
+    try!(write_f64(w, state.raw_sensor.time));
+
+    w.write_all(&[status_bits(&state.status)])
+}
+
+fn status_bits(status: &TrackingStatus) -> u8 {
+    (if status.orientation_tracked { 0x01u8 } else { 0 }) |
+    (if status.position_tracked { 0x02u8 } else { 0 }) |
+    (if status.camera_pose_tracked { 0x04u8 } else { 0 }) |
+    (if status.position_connected { 0x08u8 } else { 0 }) |
+    (if status.hmd_connected { 0x10u8 } else { 0 })
+}
+
+fn status_from_bits(bits: u8) -> TrackingStatus {
+    TrackingStatus {
+        orientation_tracked: bits & 0x01 != 0,
+        position_tracked: bits & 0x02 != 0,
+        camera_pose_tracked: bits & 0x04 != 0,
+        position_connected: bits & 0x08 != 0,
+        hmd_connected: bits & 0x10 != 0
+    }
+}
+
+fn read_u8<R: Read>(r: &mut R) -> io::Result<u8> {
+    let mut buf = [0u8; 1];
+    try!(r.read_exact(&mut buf));
+    Ok(buf[0])
+}
+
+fn read_u16<R: Read>(r: &mut R) -> io::Result<u16> {
+    let mut buf = [0u8; 2];
+    try!(r.read_exact(&mut buf));
+    Ok(buf[0] as u16 | (buf[1] as u16) << 8)
+}
+
+fn read_u32<R: Read>(r: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    try!(r.read_exact(&mut buf));
+    Ok(buf[0] as u32 | (buf[1] as u32) << 8 | (buf[2] as u32) << 16 | (buf[3] as u32) << 24)
+}
+
+fn read_f32<R: Read>(r: &mut R) -> io::Result<f32> {
+    let bits = try!(read_u32(r));
+    Ok(unsafe { mem::transmute::<u32, f32>(bits) })
+}
+
+fn read_f64<R: Read>(r: &mut R) -> io::Result<f64> {
+    let low = try!(read_u32(r)) as u64;
+    let high = try!(read_u32(r)) as u64;
+    Ok(unsafe { mem::transmute::<u64, f64>(low | high << 32) })
+}
+
+fn read_vector3<R: Read>(r: &mut R) -> io::Result<Vector3> {
+    Ok([try!(read_f32(r)), try!(read_f32(r)), try!(read_f32(r))])
+}
+
+fn read_quaternion<R: Read>(r: &mut R) -> io::Result<Quaternion> {
+    let w = try!(read_f32(r));
+    Ok((w, try!(read_vector3(r))))
+}
+
+fn read_pose<R: Read>(r: &mut R) -> io::Result<Pose> {
+    let orientation = try!(read_quaternion(r));
+    Ok(Pose {
+        orientation: orientation,
+        position: try!(read_vector3(r))
+    })
+}
+
+/// Read a tracking state in the recording format.
+#[doc(hidden)]
+pub fn read_tracking_state<R: Read>(r: &mut R) -> io::Result<TrackingState> {
+    let head = PoseState {
+        pose: try!(read_pose(r)),
+        angular_velocity: try!(read_vector3(r)),
+        linear_velocity: try!(read_vector3(r)),
+        angular_acceleration: try!(read_vector3(r)),
+        linear_acceleration: try!(read_vector3(r)),
+        time: try!(read_f64(r))
+    };
+    let camera_pose = try!(read_pose(r));
+    let leveled_camera_pose = try!(read_pose(r));
+    let raw_sensor = SensorData {
+        accelerometer: try!(read_vector3(r)),
+        gyro: try!(read_vector3(r)),
+        magnetometer: try!(read_vector3(r)),
+        temperature: try!(read_f32(r)),
+        time: try!(read_f64(r))
+    };
+    let bits = try!(read_u8(r));
+    Ok(TrackingState {
+        head: head,
+        camera_pose: camera_pose,
+        leveled_camera_pose: leveled_camera_pose,
+        raw_sensor: raw_sensor,
+        status: status_from_bits(bits)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use Eye;
+    use shim::{FrameEyePose, TrackingState};
+    use super::{Playback, Record, Recorder, Recording, Replay, TRACKING_STATE_BYTES};
+    use super::write_tracking_state;
+
+    fn eye_pose(eye: Eye, x: f32) -> FrameEyePose {
+        FrameEyePose {
+            eye: eye,
+            orientation: (1f32, [0f32; 3]),
+            position: [x, 1.6f32, 0f32],
+            status: Default::default()
+        }
+    }
+
+    fn recording(frame_times: &[f64]) -> Recording {
+        let mut recorder = Recorder::new(Vec::new()).unwrap();
+        for &time in frame_times.iter() {
+            recorder.record_poses(time,
+                                  &TrackingState::default(),
+                                  &[eye_pose(Eye::Left, time as f32)]).unwrap();
+        }
+        let bytes = recorder.finish().unwrap();
+        Recording::read(&mut &bytes[..]).unwrap()
+    }
+
+    fn read_error(bytes: &[u8]) -> io::ErrorKind {
+        Recording::read(&mut &bytes[..]).unwrap_err().kind()
+    }
+
+    #[test]
+    fn frame_round_trip() {
+        let mut state = TrackingState::default();
+        state.head.pose.position = [0.1f32, 1.7f32, -0.3f32];
+        state.status.position_tracked = true;
+        let poses = [eye_pose(Eye::Right, 0.032f32), eye_pose(Eye::Left, -0.032f32)];
+
+        let mut recorder = Recorder::new(Vec::new()).unwrap();
+        recorder.record_poses(2.5f64, &state, &poses).unwrap();
+        let bytes = recorder.finish().unwrap();
+
+        let recording = Recording::read(&mut &bytes[..]).unwrap();
+        let frames = recording.frames();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].time, 2.5f64);
+        assert_eq!(frames[0].state, state);
+        assert_eq!(frames[0].eye_poses.len(), 2);
+        assert_eq!(frames[0].eye_poses[0].eye, Eye::Right);
+        assert_eq!(frames[0].eye_poses[0].position, poses[0].position);
+        assert_eq!(frames[0].eye_poses[1].eye, Eye::Left);
+        assert!(!frames[0].eye_poses[1].status.position_tracked);
+    }
+
+    #[test]
+    fn eye_pose_status_round_trip() {
+        let mut left = eye_pose(Eye::Left, -0.032f32);
+        left.status.orientation_tracked = true;
+        let mut right = eye_pose(Eye::Right, 0.032f32);
+        right.status.hmd_connected = true;
+
+        let mut recorder = Recorder::new(Vec::new()).unwrap();
+        recorder.record_poses(1f64, &TrackingState::default(), &[left, right]).unwrap();
+        let bytes = recorder.finish().unwrap();
+
+        let recording = Recording::read(&mut &bytes[..]).unwrap();
+        let ref eye_poses = recording.frames()[0].eye_poses;
+        assert_eq!(eye_poses[0].status, left.status);
+        assert_eq!(eye_poses[1].status, right.status);
+    }
+
+    #[test]
+    fn tracking_state_size() {
+        let mut bytes = Vec::new();
+        write_tracking_state(&mut bytes, &TrackingState::default()).unwrap();
+        assert_eq!(bytes.len(), TRACKING_STATE_BYTES);
+    }
+
+    #[test]
+    fn records_keep_their_order() {
+        let mut recorder = Recorder::new(Vec::new()).unwrap();
+        recorder.record_tracking_state(1f64, &TrackingState::default()).unwrap();
+        recorder.record_poses(2f64, &TrackingState::default(), &[]).unwrap();
+        recorder.record_tracking_state(3f64, &TrackingState::default()).unwrap();
+        let bytes = recorder.finish().unwrap();
+
+        let recording = Recording::read(&mut &bytes[..]).unwrap();
+        let times: Vec<f64> = recording.records.iter().map(|record| match record {
+            &Record::Frame(ref frame) => frame.time,
+            &Record::TrackingState(ref state) => state.time
+        }).collect();
+        assert_eq!(times, vec![1f64, 2f64, 3f64]);
+    }
+
+    #[test]
+    fn bad_magic() {
+        assert_eq!(read_error(b"RVRX\x01\x00"), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn bad_version() {
+        assert_eq!(read_error(b"RVRC\x01\x00"), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn truncated_input() {
+        let mut recorder = Recorder::new(Vec::new()).unwrap();
+        recorder.record_tracking_state(1f64, &TrackingState::default()).unwrap();
+        let bytes = recorder.finish().unwrap();
+
+        assert_eq!(read_error(&bytes[..3]), io::ErrorKind::UnexpectedEof);
+        assert_eq!(read_error(&bytes[..bytes.len() - 1]), io::ErrorKind::UnexpectedEof);
+
+        // a header alone is an empty recording
+        assert_eq!(Recording::read(&mut &bytes[..6]).unwrap().records.len(), 0);
+    }
+
+    #[test]
+    fn stepped_replay() {
+        let mut replay = Replay::new(recording(&[0f64, 0f64, 1000f64]), Playback::Stepped);
+        let mut positions = Vec::new();
+        for _ in 0..4 {
+            replay.next_frame();
+            positions.push(replay.position().unwrap());
+        }
+        assert_eq!(positions, vec![0, 1, 2, 2]);
+        assert!(replay.is_finished());
+    }
+
+    #[test]
+    fn stepped_replay_only_advances_on_next_frame() {
+        let mut replay = Replay::new(recording(&[0f64, 1f64, 2f64]), Playback::Stepped);
+        assert_eq!(replay.current_frame().unwrap().time, 0f64);
+        assert_eq!(replay.upcoming(), Some(0));
+        replay.next_frame();
+        assert_eq!(replay.upcoming(), Some(1));
+        assert_eq!(replay.upcoming(), Some(1));
+        assert_eq!(replay.current_frame().unwrap().time, 0f64);
+        assert_eq!(replay.position(), Some(0));
+    }
+
+    #[test]
+    fn real_time_replay() {
+        // the second frame is due immediately, and the third not for a long time
+        let mut replay = Replay::new(recording(&[0f64, 0f64, 1000f64]), Playback::RealTime);
+        let mut positions = Vec::new();
+        for _ in 0..4 {
+            replay.next_frame();
+            positions.push(replay.position().unwrap());
+        }
+        assert_eq!(positions, vec![0, 1, 1, 1]);
+        assert!(!replay.is_finished());
+    }
+
+    #[test]
+    fn empty_replay() {
+        let mut replay = Replay::new(Recording::default(), Playback::Stepped);
+        assert!(replay.next_frame().is_none());
+        assert!(replay.is_finished());
+    }
+}
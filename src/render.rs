@@ -7,7 +7,7 @@
 //! ```no_run
 //! # extern crate rovr;
 //! # extern crate libc;
-//! # use rovr::{Context, TrackingOptions, Eye};
+//! # use rovr::{Context, TrackingOptions, RenderOptions, Eye};
 //! # use rovr::render::Frame;
 //! # fn main() {
 //! let hmd = Context::new().unwrap()
@@ -28,7 +28,7 @@
 //!
 //! // This is unsafe because of the lifetime of native_window. If the window is closed before this
 //! // render context is destroyed, bad things may happen!
-//! let rc = hmd.render_to(&render_target).unwrap();
+//! let mut rc = hmd.render_to(&render_target, &RenderOptions::new()).unwrap();
 //! let (w_left, h_left) = rc.target_texture_size(&Eye::Left);
 //! let (w_right, h_right) = rc.target_texture_size(&Eye::Right);
 //!
@@ -38,11 +38,27 @@
 //! # let (left_tex_id, right_tex_id) = (0, 0);
 //! let binding = rc.create_binding(left_tex_id, right_tex_id);
 //! loop {
-//!     let frame = Frame::new(&rc, &binding);
-//!     // draw to framebuffers; frame will finish at end of loop body
+//!     let frame = Frame::new(&mut rc, &binding).unwrap();
+//!     // draw to framebuffers, then hand them to the headset
+//!     frame.submit().unwrap();
 //! }
 //! # }
 //! ```
+//!
+//! # Client distortion
+//!
+//! A `RenderContext` created with `Hmd::render_client_distortion` leaves the final distortion
+//! pass to the application. Build each eye's mesh once with `RenderContext::distortion_mesh`,
+//! then for each frame:
+//!
+//! 1. Render both eyes as usual.
+//! 2. For each eye, draw its mesh to the window, sampling the eye texture at
+//!    `flatten(mix(start * v, end * v, timewarp_factor)) * scale + offset` for each colour
+//!    channel, where `v` is `(tan_eye_angles.x, tan_eye_angles.y, 1)`, `flatten` divides `xy` by
+//!    `z`, `(start, end)` come from `Frame::timewarp_matrices` and `(scale, offset)` from
+//!    `Frame::uv_scale_offset`. Multiply the result by `vignette_factor`.
+//! 3. Swap buffers, then submit the `Frame`. The SDK uses the time the frame is submitted for its
+//!    timing, so it's best to wait for the GPU (`glFinish`) first.
 
 pub use shim::RenderContext;
 pub use shim::TextureBinding;
@@ -51,3 +67,12 @@ pub use shim::Vector3;
 pub use shim::Matrix4;
 pub use shim::FrameEyePose;
 pub use shim::Frame;
+pub use shim::FramePoses;
+pub use shim::DistortionMesh;
+pub use shim::DistortionVertex;
+pub use shim::Viewport;
+pub use shim::FovPort;
+pub use shim::FrameTiming;
+pub use shim::LatencyTimings;
+pub use shim::SubmitInfo;
+pub use shim::FrameToken;
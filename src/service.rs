@@ -0,0 +1,302 @@
+//! Background tracking service.
+//!
+//! A `TrackingService` polls a headset's tracking state on its own thread and publishes the
+//! latest state to any number of `TrackingReader`s. Readers are `Send` and `Sync`, and cloning
+//! one only bumps a reference count, so a single reader can be shared by every thread that needs
+//! poses. Reading never blocks or takes a lock: the latest state is held in a sequence lock, and a
+//! read that overlaps a publish is repeated. A publish is a few dozen atomic stores, made at most
+//! 1000 times a second, so reads rarely repeat.
+//!
+//! ```no_run
+//! # use rovr::Context;
+//! use rovr::service::TrackingService;
+//!
+//! # let context = Context::new().unwrap();
+//! # let hmd = context.build_hmd().build().unwrap();
+//! let service = TrackingService::start(&hmd, 1000).unwrap();
+//! let reader = service.reader();
+//! let simulation = std::thread::spawn(move || {
+//!     while reader.is_running() {
+//!         if let Some(state) = reader.latest() {
+//!             // step the simulation with state.head
+//!         }
+//!     }
+//! });
+//! // the service stops polling when dropped, so it must outlive its readers' use
+//! # drop(simulation);
+//! ```
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{self, AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use Hmd;
+use OculusError;
+use record::{self, TRACKING_STATE_BYTES};
+use shim::{Tracker, TrackingState};
+
+/// Fastest supported poll rate, in samples per second. The headset's IMU samples at around
+/// 1000Hz, so polling faster would only republish the same state.
+pub const MAX_POLL_RATE: u32 = 1000;
+
+// A tracking state is stored as its recording format bytes, packed four to a word.
+const STATE_WORDS: usize = (TRACKING_STATE_BYTES + 3) / 4;
+
+/// The latest published state, behind a sequence lock. `sequence` is odd while a publish is in
+/// progress, and counts two for every completed publish.
+struct Shared {
+    sequence: AtomicUsize,
+    words: Vec<AtomicUsize>,
+    running: AtomicBool
+}
+
+impl Shared {
+    fn new() -> Shared {
+        Shared {
+            sequence: AtomicUsize::new(0),
+            words: (0..STATE_WORDS).map(|_| AtomicUsize::new(0)).collect(),
+            running: AtomicBool::new(true)
+        }
+    }
+
+    /// Publish a new state. Only the service thread publishes, so publishes never overlap.
+    fn publish(&self, state: &TrackingState) {
+        let mut bytes = [0u8; STATE_WORDS * 4];
+        record::write_tracking_state(&mut &mut bytes[..], state)
+            .expect("tracking state doesn't fit its buffer");
+
+        let sequence = self.sequence.load(Ordering::Relaxed);
+        self.sequence.store(sequence + 1, Ordering::Relaxed);
+        atomic::fence(Ordering::Release);
+        for (word, chunk) in self.words.iter().zip(bytes.chunks(4)) {
+            let value = chunk[0] as usize | (chunk[1] as usize) << 8 |
+                        (chunk[2] as usize) << 16 | (chunk[3] as usize) << 24;
+            word.store(value, Ordering::Relaxed);
+        }
+        self.sequence.store(sequence + 2, Ordering::Release);
+    }
+
+    fn latest(&self) -> Option<TrackingState> {
+        let mut bytes = [0u8; STATE_WORDS * 4];
+        loop {
+            let before = self.sequence.load(Ordering::Acquire);
+            if before == 0 {
+                return None;
+            }
+            if before % 2 == 1 {
+                continue;
+            }
+            for (word, chunk) in self.words.iter().zip(bytes.chunks_mut(4)) {
+                let value = word.load(Ordering::Relaxed);
+                chunk[0] = value as u8;
+                chunk[1] = (value >> 8) as u8;
+                chunk[2] = (value >> 16) as u8;
+                chunk[3] = (value >> 24) as u8;
+            }
+            atomic::fence(Ordering::Acquire);
+            if self.sequence.load(Ordering::Relaxed) == before {
+                break;
+            }
+        }
+        Some(record::read_tracking_state(&mut &bytes[..])
+            .expect("tracking state doesn't fit its buffer"))
+    }
+
+    fn published_count(&self) -> usize {
+        self.sequence.load(Ordering::Acquire) / 2
+    }
+}
+
+/// Handle for reading the latest tracking state published by a `TrackingService`. Readers can be
+/// shared between threads, or cloned cheaply.
+#[derive(Clone)]
+pub struct TrackingReader {
+    shared: Arc<Shared>
+}
+
+impl TrackingReader {
+    /// The most recently published tracking state, or `None` if the service hasn't published one
+    /// yet. Never blocks.
+    pub fn latest(&self) -> Option<TrackingState> {
+        self.shared.latest()
+    }
+
+    /// Number of tracking states published so far. Useful for detecting whether `latest` has
+    /// changed since it was last read.
+    pub fn published_count(&self) -> usize {
+        self.shared.published_count()
+    }
+
+    /// True while the service that feeds this reader is still running.
+    pub fn is_running(&self) -> bool {
+        self.shared.running.load(Ordering::Acquire)
+    }
+}
+
+/// Polls tracking state on a background thread. The thread stops when the service is dropped.
+pub struct TrackingService<'a> {
+    shared: Arc<Shared>,
+    thread: Option<thread::JoinHandle<()>>,
+
+    // the poll thread shares the headset's handle, so the headset must outlive it
+    _hmd_phantom: PhantomData<&'a Hmd>
+}
+
+impl<'a> TrackingService<'a> {
+    /// Start polling `hmd` at `poll_rate` samples per second. Each published state is the
+    /// headset's state at the time of polling, in the headset's current tracking space.
+    ///
+    /// # Failure
+    ///
+    /// Fails with `InvalidInput` if `poll_rate` is zero or greater than `MAX_POLL_RATE`.
+    pub fn start(hmd: &'a Hmd, poll_rate: u32) -> Result<TrackingService<'a>, OculusError> {
+        if poll_rate == 0 || poll_rate > MAX_POLL_RATE {
+            return Err(OculusError::InvalidInput("poll_rate must be between 1 and 1000"));
+        }
+        let interval = Duration::new(0, 1_000_000_000 / poll_rate);
+        let tracker = hmd.shim_hmd.tracker();
+        let shared = Arc::new(Shared::new());
+        let thread_shared = shared.clone();
+        let thread = thread::spawn(move || poll(tracker, thread_shared, interval));
+        Ok(TrackingService {
+            shared: shared,
+            thread: Some(thread),
+            _hmd_phantom: PhantomData
+        })
+    }
+
+    /// Create a reader for the states published by this service.
+    pub fn reader(&self) -> TrackingReader {
+        TrackingReader { shared: self.shared.clone() }
+    }
+}
+
+fn poll(tracker: Tracker, shared: Arc<Shared>, interval: Duration) {
+    let mut next = Instant::now();
+    while shared.running.load(Ordering::Acquire) {
+        let state = tracker.tracking_state(tracker.time_seconds());
+        shared.publish(&state);
+
+        // poll on a fixed schedule, so time spent polling doesn't lower the rate; after falling
+        // behind, start the schedule over rather than polling in a burst to catch up
+        next = next + interval;
+        let now = Instant::now();
+        if next > now {
+            thread::sleep(next - now);
+        } else {
+            next = now;
+        }
+    }
+}
+
+impl<'a> Drop for TrackingService<'a> {
+    fn drop(&mut self) {
+        self.shared.running.store(false, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::Ordering;
+    use std::thread;
+
+    use shim::TrackingState;
+    use super::{Shared, TrackingReader};
+
+    fn state(i: usize) -> TrackingState {
+        let mut state = TrackingState::default();
+        state.head.time = i as f64;
+        state.head.pose.position = [i as f32; 3];
+        state.raw_sensor.time = i as f64;
+        state.status.hmd_connected = i % 2 == 1;
+        state
+    }
+
+    fn reader(shared: &Arc<Shared>) -> TrackingReader {
+        TrackingReader { shared: shared.clone() }
+    }
+
+    #[test]
+    fn readers_are_shareable() {
+        fn check<T: Send + Sync + Clone>() {}
+        check::<TrackingReader>();
+    }
+
+    #[test]
+    fn nothing_published() {
+        let shared = Arc::new(Shared::new());
+        let reader = reader(&shared);
+        assert_eq!(reader.latest(), None);
+        assert_eq!(reader.published_count(), 0);
+    }
+
+    #[test]
+    fn latest_state() {
+        let shared = Arc::new(Shared::new());
+        let reader = reader(&shared);
+        shared.publish(&state(1));
+        assert_eq!(reader.latest(), Some(state(1)));
+
+        // reading again without a new publish returns the same state
+        assert_eq!(reader.latest(), Some(state(1)));
+        assert_eq!(reader.published_count(), 1);
+
+        shared.publish(&state(2));
+        assert_eq!(reader.latest(), Some(state(2)));
+        assert_eq!(reader.published_count(), 2);
+    }
+
+    #[test]
+    fn clones_share_state() {
+        let shared = Arc::new(Shared::new());
+        let reader = reader(&shared);
+        shared.publish(&state(3));
+        let clone = reader.clone();
+        assert_eq!(clone.latest(), Some(state(3)));
+
+        shared.publish(&state(4));
+        assert_eq!(clone.latest(), Some(state(4)));
+        assert_eq!(reader.latest(), Some(state(4)));
+        assert_eq!(Arc::strong_count(&shared), 3);
+    }
+
+    #[test]
+    fn readers_racing_writer() {
+        let shared = Arc::new(Shared::new());
+        let reader = reader(&shared);
+        let writer_shared = shared.clone();
+        let writer = thread::spawn(move || {
+            for i in 1..20000 {
+                writer_shared.publish(&state(i));
+            }
+            writer_shared.running.store(false, Ordering::Release);
+        });
+
+        // every state read must be one that was published whole, and never older than the last
+        let readers: Vec<_> = (0..2).map(|_| {
+            let reader = reader.clone();
+            thread::spawn(move || {
+                let mut last = 0f64;
+                while reader.is_running() {
+                    if let Some(read) = reader.latest() {
+                        let i = read.head.time;
+                        assert_eq!(read, state(i as usize));
+                        assert!(i >= last);
+                        last = i;
+                    }
+                }
+            })
+        }).collect();
+        writer.join().unwrap();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        assert_eq!(reader.latest(), Some(state(19999)));
+    }
+}
@@ -1,21 +1,30 @@
 //! Safe shim directly over the Oculus SDK. More or less directly exposes the Oculus "way" of
 //! interfacing with an HMD and handling rendering.
 
+use std::cmp;
 use std::ptr;
 use std::default::Default;
 use ffi::UnsafeDynamicLibrary;
+use std::cell::Cell;
 use std::marker::PhantomData;
-use std::rc::Rc;
+use std::mem;
 use std::string::String;
-use std::sync::atomic;
+use std::sync::{atomic, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 use std::vec;
 
 use libc;
 
 use ffi;
+use math;
+use record::{RecordedFrame, Replay};
+use tracking::TrackingSpace;
 use OculusError;
 use Eye;
 use RenderTarget;
+use RenderOptions;
+use ProjectionOptions;
 
 /// A quaternion. The first element of the tuple is the w value, and the array contains x, y, and z
 /// values.
@@ -27,6 +36,47 @@ pub type Vector3 = [f32; 3];
 /// A 4x4 matrix, by convention in column-major format.
 pub type Matrix4 = [[f32; 4]; 4];
 
+fn quaternion(q: &ffi::ovrQuatf) -> Quaternion {
+    (q.w, [q.x, q.y, q.z])
+}
+
+fn vector3(v: &ffi::ovrVector3f) -> Vector3 {
+    [v.x, v.y, v.z]
+}
+
+fn eye_index(eye: &Eye) -> usize {
+    match eye {
+        &Eye::Left => 0,
+        &Eye::Right => 1
+    }
+}
+
+/// Convert a row-major SDK matrix to a column-major `Matrix4`.
+fn matrix4(m: &ffi::ovrMatrix4f) -> Matrix4 {
+    let ref pm = m.M;
+    [[pm[0][0], pm[1][0], pm[2][0], pm[3][0]],
+     [pm[0][1], pm[1][1], pm[2][1], pm[3][1]],
+     [pm[0][2], pm[1][2], pm[2][2], pm[3][2]],
+     [pm[0][3], pm[1][3], pm[2][3], pm[3][3]]]
+}
+
+fn native_matrix4(m: &Matrix4) -> ffi::ovrMatrix4f {
+    ffi::ovrMatrix4f {
+        M: [[m[0][0], m[1][0], m[2][0], m[3][0]],
+            [m[0][1], m[1][1], m[2][1], m[3][1]],
+            [m[0][2], m[1][2], m[2][2], m[3][2]],
+            [m[0][3], m[1][3], m[2][3], m[3][3]]]
+    }
+}
+
+fn native_pose(orientation: &Quaternion, position: &Vector3) -> ffi::ovrPosef {
+    let (w, ref v) = *orientation;
+    ffi::ovrPosef {
+        Orientation: ffi::ovrQuatf { x: v[0], y: v[1], z: v[2], w: w },
+        Position: ffi::ovrVector3f { x: position[0], y: position[1], z: position[2] }
+    }
+}
+
 /// Invoke an FFI function with an ovrBool return value, yielding OculusError::SdkError with the
 /// supplied message on failure.
 macro_rules! ovr_invoke {
@@ -125,6 +175,23 @@ impl Context {
     pub fn invoker(&self) -> &ffi::FunctionTable {
         &self.function_table
     }
+
+    /// Current time on the SDK clock, in seconds. This is the timebase used for all tracking
+    /// timestamps and predictions.
+    pub fn time_seconds(&self) -> f64 {
+        unsafe {
+            self.invoker().ovr_GetTimeInSeconds()
+        }
+    }
+
+    /// Version string reported by the loaded Oculus runtime.
+    pub fn version_string(&self) -> String {
+        unsafe {
+            use std::ffi::CStr;
+            let s = CStr::from_ptr(self.invoker().ovr_GetVersionString()).to_bytes();
+            String::from_utf8_lossy(s).into_owned()
+        }
+    }
 }
 
 impl Drop for Context {
@@ -140,6 +207,7 @@ impl Drop for Context {
 /// Platform-specific identifier for the OS display representing an Hmd.
 #[allow(dead_code)] // Per-platform, only one of these enum values is used.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum HmdDisplayId {
     /// On OS X, this value is the display ID as it would be returned from
     /// `CGGetActiveDisplayList`.
@@ -154,6 +222,8 @@ pub enum HmdDisplayId {
 
 /// Full details about the system display representing this Hmd. These should be used to find the
 /// correct monitor on which to prepare a rendering window.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HmdDisplay {
     /// Identifier for this monitor, if available.
     pub id: HmdDisplayId,
@@ -171,17 +241,259 @@ pub struct HmdDisplay {
     pub height: u32
 }
 
+/// The model of headset represented by an Hmd.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HmdType {
+    DK1,
+    DKHD,
+    DK2,
+    BlackStar,
+    CrescentBay,
+
+    /// A headset the runtime knows about but doesn't identify as one of the above.
+    Other
+}
+
+impl HmdType {
+    fn from_native(native_type: ffi::ovrHmdType) -> HmdType {
+        match native_type {
+            ffi::ovrHmd_DK1 => HmdType::DK1,
+            ffi::ovrHmd_DKHD => HmdType::DKHD,
+            ffi::ovrHmd_DK2 => HmdType::DK2,
+            ffi::ovrHmd_BlackStar => HmdType::BlackStar,
+            ffi::ovrHmd_CB => HmdType::CrescentBay,
+            _ => HmdType::Other
+        }
+    }
+}
+
+/// Headset capabilities, as reported by the runtime. Some of these reflect the current state of
+/// the headset (e.g. `available`), and some reflect settings applied when building the `Hmd`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HmdCapabilities {
+    /// The headset is plugged in and detected by the system.
+    pub present: bool,
+
+    /// The headset is available for use, i.e. not owned by another application.
+    pub available: bool,
+
+    /// This application has captured the headset.
+    pub captured: bool,
+
+    /// The headset is running in Extend Desktop mode rather than Direct mode.
+    pub extend_desktop: bool,
+
+    /// Output to the headset display is turned off.
+    pub display_off: bool,
+
+    /// Low persistence mode is enabled.
+    pub low_persistence: bool,
+
+    /// Tracking prediction is adjusted dynamically based on measured latency.
+    pub dynamic_prediction: bool,
+
+    /// VSync is disabled.
+    pub no_vsync: bool,
+
+    /// Headset output is not mirrored to the attached window.
+    pub no_mirror_to_window: bool
+}
+
+impl HmdCapabilities {
+    fn from_native(caps: ffi::ovrHmdCaps) -> HmdCapabilities {
+        HmdCapabilities {
+            present: caps.contains(ffi::ovrHmdCap_Present),
+            available: caps.contains(ffi::ovrHmdCap_Available),
+            captured: caps.contains(ffi::ovrHmdCap_Captured),
+            extend_desktop: caps.contains(ffi::ovrHmdCap_ExtendDesktop),
+            display_off: caps.contains(ffi::ovrHmdCap_DisplayOff),
+            low_persistence: caps.contains(ffi::ovrHmdCap_LowPersistence),
+            dynamic_prediction: caps.contains(ffi::ovrHmdCap_DynamicPrediction),
+            no_vsync: caps.contains(ffi::ovrHmdCap_NoVSync),
+            no_mirror_to_window: caps.contains(ffi::ovrHmdCap_NoMirrorToWindow)
+        }
+    }
+}
+
+/// Tracking capabilities supported by a headset.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrackingCapabilities {
+    /// Head orientation tracking.
+    pub orientation: bool,
+
+    /// Yaw drift correction using the magnetometer.
+    pub mag_yaw_correction: bool,
+
+    /// Head position tracking using the external camera.
+    pub position: bool
+}
+
+impl TrackingCapabilities {
+    fn from_native(caps: ffi::ovrTrackingCaps) -> TrackingCapabilities {
+        TrackingCapabilities {
+            orientation: caps.contains(ffi::ovrTrackingCap_Orientation),
+            mag_yaw_correction: caps.contains(ffi::ovrTrackingCap_MagYawCorrection),
+            position: caps.contains(ffi::ovrTrackingCap_Position)
+        }
+    }
+}
+
+/// Field of view and clipping range of the positional tracking camera.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CameraFrustum {
+    /// Horizontal field of view, in radians.
+    pub h_fov: f32,
+
+    /// Vertical field of view, in radians.
+    pub v_fov: f32,
+
+    /// Distance to the near plane, in meters.
+    pub near_z: f32,
+
+    /// Distance to the far plane, in meters.
+    pub far_z: f32
+}
+
+/// Static description of a headset: what it is, what it can do, and how it is connected.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HmdDescriptor {
+    /// Headset model.
+    pub hmd_type: HmdType,
+
+    /// Human-readable product name, e.g. "Oculus Rift DK2".
+    pub product_name: String,
+
+    /// Human-readable manufacturer name.
+    pub manufacturer: String,
+
+    /// USB vendor ID.
+    pub vendor_id: u16,
+
+    /// USB product ID.
+    pub product_id: u16,
+
+    /// Headset serial number.
+    pub serial_number: String,
+
+    /// `(major, minor)` firmware version.
+    pub firmware_version: (u16, u16),
+
+    /// Tracking camera frustum. All zeroes if the headset has no positional tracking.
+    pub camera_frustum: CameraFrustum,
+
+    /// Current headset capabilities.
+    pub hmd_caps: HmdCapabilities,
+
+    /// Tracking capabilities supported by the headset.
+    pub tracking_caps: TrackingCapabilities,
+
+    /// `(width, height)` native resolution of the headset display.
+    pub resolution: (u32, u32)
+}
+
+/// Owned native headset handle. The headset is destroyed once the owning `Hmd` and all `Tracker`s
+/// sharing this handle are dropped.
+struct HmdHandle {
+    native_hmd: *mut ffi::ovrHmdDesc,
+    context: Arc<Context>,
+    tracking_space: Mutex<TrackingSpace>,
+    replay: Mutex<Option<Replay>>
+}
+
+impl Drop for HmdHandle {
+    fn drop(&mut self) {
+        unsafe {
+            self.context.invoker().ovrHmd_Destroy(self.native_hmd);
+        }
+    }
+}
+
+/// Handle for querying a headset's tracking state from another thread. Obtained from
+/// `Hmd::tracker()`; keeps the headset open for as long as it is alive.
+#[derive(Clone)]
+pub struct Tracker {
+    handle: Arc<HmdHandle>
+}
+
+// A `Tracker` only reaches `ovrHmd_GetTrackingState` and `ovr_GetTimeInSeconds`, which the SDK
+// documents as thread-safe, and the tracking space and replay, which are behind locks. Trackers
+// only leave the crate's control on `TrackingService` threads, which are joined before the `Hmd`
+// they borrow can be dropped, so the last handle, and with it `ovrHmd_Destroy`, always goes with
+// the `Hmd` on its owner's thread.
+//
+// This also makes `Hmd` `Send`. That's sound: moving an `Hmd` moves its single owner, and every
+// SDK call that isn't thread-safe is still only made by that owner. `Hmd` stays `!Sync`, since
+// the handle holds a raw pointer, so those calls can never be made from two threads at once.
+unsafe impl Send for Tracker {}
+
+impl Tracker {
+    /// Tracking state predicted for `abs_time`, in seconds on the SDK clock, in the headset's
+    /// current tracking space. An `abs_time` of 0 returns the most recent tracking state without
+    /// prediction. While a replay is running, returns the current recorded state instead.
+    pub fn tracking_state(&self, abs_time: f64) -> TrackingState {
+        // recorded states are already in the tracking space the application saw at the time
+        let replayed = self.with_replay(|replay| replay.current_frame().map(|frame| frame.state));
+        match replayed {
+            Some(Some(state)) => state,
+            _ => self.tracking_space().transform_state(&self.native_tracking_state(abs_time))
+        }
+    }
+
+    /// Like `tracking_state`, but in the SDK's native tracking space.
+    fn native_tracking_state(&self, abs_time: f64) -> TrackingState {
+        let state = unsafe {
+            let ref handle = self.handle;
+            handle.context.invoker().ovrHmd_GetTrackingState(handle.native_hmd, abs_time)
+        };
+        TrackingState::from_native(&state)
+    }
+
+    /// The tracking space in which poses are reported.
+    pub fn tracking_space(&self) -> TrackingSpace {
+        // the space is only ever replaced whole, so a poisoned lock still holds a valid space
+        *self.handle.tracking_space.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Set the tracking space in which poses are reported.
+    pub fn set_tracking_space(&self, space: TrackingSpace) {
+        *self.handle.tracking_space.lock().unwrap_or_else(|e| e.into_inner()) = space;
+    }
+
+    /// Replace the replay that poses are read from, returning the previous one. `None` returns
+    /// to live tracking.
+    fn set_replay(&self, replay: Option<Replay>) -> Option<Replay> {
+        let mut current = self.handle.replay.lock().unwrap_or_else(|e| e.into_inner());
+        mem::replace(&mut *current, replay)
+    }
+
+    /// Run `f` on the running replay, if any.
+    fn with_replay<T, F: FnOnce(&mut Replay) -> T>(&self, f: F) -> Option<T> {
+        // a replay only moves between valid positions, so a poisoned lock still holds a valid one
+        let mut replay = self.handle.replay.lock().unwrap_or_else(|e| e.into_inner());
+        replay.as_mut().map(f)
+    }
+
+    /// Current time on the SDK clock, in seconds.
+    pub fn time_seconds(&self) -> f64 {
+        self.handle.context.time_seconds()
+    }
+}
+
 /// RAII wrapper for an Oculus headset. Provides safe wrappers for access to basic headset
 /// metadata and tracking state.
 pub struct Hmd {
-    native_hmd: *mut ffi::ovrHmdDesc,
-    context: Rc<Context>
+    tracker: Tracker
 }
 
 impl Hmd {
     /// Create a new HMD. If `allow_debug` is true and no headset is otherwise detected, a fake
     /// "debug" HMD instance will be returned instead.
-    pub fn new(allow_debug: bool, owning_context: Rc<Context>) -> Result<Hmd, OculusError> {
+    pub fn new(allow_debug: bool, owning_context: Arc<Context>) -> Result<Hmd, OculusError> {
         let hmd = {
             unsafe {
                 let h = owning_context.invoker().ovrHmd_Create(0);
@@ -195,15 +507,69 @@ impl Hmd {
         if hmd.is_null() { 
             Err(OculusError::SdkError("ovrHmd_Create failed"))
         } else { 
-            Ok(Hmd{ native_hmd: hmd, context: owning_context })
+            Ok(Hmd{
+                tracker: Tracker {
+                    handle: Arc::new(HmdHandle {
+                        native_hmd: hmd,
+                        context: owning_context,
+                        tracking_space: Mutex::new(TrackingSpace::new()),
+                        replay: Mutex::new(None)
+                    })
+                }
+            })
+        }
+    }
+
+    fn native(&self) -> *mut ffi::ovrHmdDesc {
+        self.tracker.handle.native_hmd
+    }
+
+    fn invoker(&self) -> &ffi::FunctionTable {
+        self.tracker.handle.context.invoker()
+    }
+
+    /// Most recent error reported by the SDK for this headset, if any.
+    fn last_error(&self) -> Option<String> {
+        unsafe {
+            use std::ffi::CStr;
+            let error = self.invoker().ovrHmd_GetLastError(self.native());
+            if error.is_null() {
+                return None;
+            }
+            let s = CStr::from_ptr(error).to_bytes();
+            if s.is_empty() {
+                None
+            } else {
+                Some(String::from_utf8_lossy(s).into_owned())
+            }
         }
     }
 
+    /// A handle for querying this headset's tracking state from another thread.
+    pub fn tracker(&self) -> Tracker {
+        self.tracker.clone()
+    }
+
     /// Set HMD caps. Some HMD caps cannot be set using the Oculus SDK; see the Oculus docs for
     /// more details.
     pub fn set_caps(&mut self, caps: ffi::ovrHmdCaps) {
         unsafe {
-            self.context.invoker().ovrHmd_SetEnabledCaps(self.native_hmd, caps);
+            self.invoker().ovrHmd_SetEnabledCaps(self.native(), caps);
+        }
+    }
+
+    /// Turn mirroring of headset output to the attached window on or off, leaving other
+    /// capabilities unchanged.
+    pub fn set_mirroring(&self, enabled: bool) {
+        unsafe {
+            let invoker = self.invoker();
+            let mut caps = invoker.ovrHmd_GetEnabledCaps(self.native());
+            if enabled {
+                caps.remove(ffi::ovrHmdCap_NoMirrorToWindow);
+            } else {
+                caps.insert(ffi::ovrHmdCap_NoMirrorToWindow);
+            }
+            invoker.ovrHmd_SetEnabledCaps(self.native(), caps & ffi::ovrHmdCap_Writable_Mask);
         }
     }
 
@@ -214,31 +580,286 @@ impl Hmd {
         unsafe {
             // Ignore the return value; the underlying implementation is already idempotent, and
             // queues up the dismissal if it isn't ready yet.
-            self.context.invoker().ovrHmd_DismissHSWDisplay(self.native_hmd);
+            self.invoker().ovrHmd_DismissHSWDisplay(self.native());
+        }
+    }
+
+    /// Current state of the Health and Safety warning.
+    pub fn hsw_state(&self) -> HswState {
+        let mut state: ffi::ovrHSWDisplayState = Default::default();
+        unsafe {
+            self.invoker().ovrHmd_GetHSWDisplayState(self.native(), &mut state);
+        }
+        HswState {
+            displayed: state.Displayed != ffi::ovrFalse,
+            start_time: state.StartTime,
+            dismissible_time: state.DismissibleTime
         }
     }
 
+    /// Recenter the headset, using the current orientation and position as the origin. Also
+    /// clears any yaw correction from `recenter_yaw`, which would otherwise be applied on top of
+    /// the new origin.
     pub fn recenter_pose(&self) {
         unsafe {
-            self.context.invoker().ovrHmd_RecenterPose(self.native_hmd);
+            self.invoker().ovrHmd_RecenterPose(self.native());
+        }
+        let mut space = self.tracking_space();
+        space.clear_yaw();
+        self.set_tracking_space(space);
+    }
+
+    /// Tracking state predicted for `abs_time`, in seconds on the SDK clock. An `abs_time` of 0
+    /// returns the most recent tracking state without prediction.
+    pub fn tracking_state(&self, abs_time: f64) -> TrackingState {
+        self.tracker.tracking_state(abs_time)
+    }
+
+    /// The tracking space in which poses are reported.
+    pub fn tracking_space(&self) -> TrackingSpace {
+        self.tracker.tracking_space()
+    }
+
+    /// Set the tracking space in which poses are reported.
+    pub fn set_tracking_space(&self, space: TrackingSpace) {
+        self.tracker.set_tracking_space(space);
+    }
+
+    /// Read poses and tracking state from `replay` rather than from the headset, until
+    /// `stop_replay` is called. Replaces any replay already running.
+    pub fn start_replay(&self, replay: Replay) {
+        self.tracker.set_replay(Some(replay));
+    }
+
+    /// Return to live tracking. Returns the replay that was running, if any.
+    pub fn stop_replay(&self) -> Option<Replay> {
+        self.tracker.set_replay(None)
+    }
+
+    /// The recorded frame that the next rendered frame would use, and its position in the
+    /// replay, or `None` if no replay is running.
+    fn upcoming_replay_frame(&self) -> Option<(usize, RecordedFrame)> {
+        let upcoming = self.tracker.with_replay(|replay| {
+            replay.upcoming().and_then(|p| replay.frame(p).map(|frame| (p, frame.clone())))
+        });
+        upcoming.and_then(|frame| frame)
+    }
+
+    /// Move the running replay, if any, to the recorded frame at `position`.
+    fn advance_replay(&self, position: usize) {
+        self.tracker.with_replay(|replay| replay.advance_to(position));
+    }
+
+    /// Rotate the tracking space so the current head orientation faces forward, leaving position,
+    /// pitch and roll unchanged.
+    pub fn recenter_yaw(&self) {
+        let head = self.tracker.native_tracking_state(0f64).head.pose;
+        let mut space = self.tracking_space();
+        space.recenter_yaw(&head);
+        self.set_tracking_space(space);
+    }
+
+    /// Eye height configured in the user's profile, in meters. Falls back to the SDK's default
+    /// if no profile is set up.
+    pub fn profile_eye_height(&self) -> f32 {
+        unsafe {
+            self.invoker().ovrHmd_GetFloat(
+                self.native(),
+                ffi::OVR_KEY_EYE_HEIGHT.as_ptr() as *const libc::c_char,
+                ffi::OVR_DEFAULT_EYE_HEIGHT)
+        }
+    }
+
+    /// Colour to draw in the latency test region this frame, or `None` if no latency test is
+    /// running.
+    pub fn latency_test_color(&self) -> Option<[u8; 3]> {
+        let mut color = [0u8; 3];
+        let drawing = unsafe {
+            self.invoker().ovrHmd_GetLatencyTest2DrawColor(self.native(), &mut color)
+        };
+        if drawing == ffi::ovrFalse { None } else { Some(color) }
+    }
+
+    /// Latencies most recently measured by the DK2 latency tester, or `None` if the headset has no
+    /// latency tester or hasn't measured anything yet.
+    pub fn latency_timings(&self) -> Option<LatencyTimings> {
+        let mut values = [0f32; 5];
+        let count = unsafe {
+            self.invoker().ovrHmd_GetFloatArray(
+                self.native(),
+                ffi::OVR_KEY_DK2_LATENCY.as_ptr() as *const libc::c_char,
+                values.as_mut_ptr(),
+                values.len() as u32)
+        };
+        // a result with fewer than all five values is incomplete, not a measurement
+        if count != values.len() as u32 || values.iter().all(|v| *v == 0f32) {
+            return None;
+        }
+        Some(LatencyTimings {
+            render: values[0],
+            timewarp: values[1],
+            post_present: values[2],
+            render_error: values[3],
+            timewarp_error: values[4]
+        })
+    }
+
+    /// Head and eye poses predicted for `abs_time`, in seconds on the SDK clock. Eye poses are
+    /// derived from the head pose using the headset's default eye offsets.
+    pub fn predict_pose(&self, abs_time: f64) -> PredictedPose {
+        let state = self.tracking_state(abs_time);
+        let offsets = unsafe {
+            let invoker = self.invoker();
+            let ref native_struct = *self.native();
+            [invoker.ovrHmd_GetRenderDesc(self.native(), 0, native_struct.DefaultEyeFov[0])
+                 .HmdToEyeViewOffset,
+             invoker.ovrHmd_GetRenderDesc(self.native(), 1, native_struct.DefaultEyeFov[1])
+                 .HmdToEyeViewOffset]
+        };
+        let eye_pose = |eye: Eye, offset: &ffi::ovrVector3f| {
+            let ref head = state.head.pose;
+            let shift = math::quat_rotate(&head.orientation, &vector3(offset));
+            FrameEyePose {
+                eye: eye,
+                orientation: head.orientation,
+                position: math::add(&head.position, &shift),
+                status: state.status
+            }
+        };
+        PredictedPose {
+            eye_poses: [eye_pose(Eye::Left, &offsets[0]), eye_pose(Eye::Right, &offsets[1])],
+            state: state
+        }
+    }
+
+    /// Current time on the SDK clock, in seconds.
+    pub fn time_seconds(&self) -> f64 {
+        self.tracker.time_seconds()
+    }
+
+    /// Enable tracking for this HMD with the specified capabilities. Fails if any of `required`
+    /// is unavailable; `required` is implicitly included in `caps`.
+    pub fn configure_tracking(&mut self,
+                              caps: ffi::ovrTrackingCaps,
+                              required: ffi::ovrTrackingCaps) -> Result<(), OculusError> {
+        let named_caps = [(ffi::ovrTrackingCap_Orientation, "orientation"),
+                          (ffi::ovrTrackingCap_MagYawCorrection, "mag_yaw_correction"),
+                          (ffi::ovrTrackingCap_Position, "position")];
+        let available = unsafe { (*self.native()).TrackingCaps };
+        for &(cap, name) in named_caps.iter() {
+            if required.contains(cap) && !available.contains(cap) {
+                return Err(OculusError::MissingTrackingCapability(name));
+            }
+        }
+
+        let result = unsafe {
+            self.invoker().ovrHmd_ConfigureTracking(self.native(), caps | required, required)
+        };
+        if result == ffi::ovrFalse {
+            // The headset reports the capability, but the runtime couldn't enable it. The SDK
+            // doesn't say which one failed, so check whether the hardware each required capability
+            // depends on is connected.
+            if required.is_empty() {
+                return Err(OculusError::SdkError("ovrHmd_ConfigureTracking failed"));
+            }
+            let status = unsafe {
+                self.invoker().ovrHmd_GetTrackingState(self.native(), 0f64).StatusFlags
+            };
+            let disconnected = [
+                (ffi::ovrTrackingCap_Orientation,
+                 ffi::ovrStatus_HmdConnected,
+                 "orientation (headset disconnected)"),
+                (ffi::ovrTrackingCap_MagYawCorrection,
+                 ffi::ovrStatus_HmdConnected,
+                 "mag_yaw_correction (headset disconnected)"),
+                (ffi::ovrTrackingCap_Position,
+                 ffi::ovrStatus_PositionConnected,
+                 "position (tracking camera disconnected)")];
+            for &(cap, connected, name) in disconnected.iter() {
+                if required.contains(cap) && !status.contains(connected) {
+                    return Err(OculusError::MissingTrackingCapability(name));
+                }
+            }
+            return Err(OculusError::MissingTrackingCapability("one or more required capabilities"));
         }
+        Ok(())
+    }
+
+    /// Default field of view for the given eye, as recommended by the SDK.
+    pub fn default_fov(&self, eye: &Eye) -> FovPort {
+        unsafe { FovPort::from_native(&(*self.native()).DefaultEyeFov[eye_index(eye)]) }
+    }
+
+    /// Largest field of view the given eye can be rendered with.
+    pub fn max_fov(&self, eye: &Eye) -> FovPort {
+        unsafe { FovPort::from_native(&(*self.native()).MaxEyeFov[eye_index(eye)]) }
+    }
+
+    /// Field of view for each eye: the requested one if given, otherwise the maximum.
+    fn eye_fovs(&self, requested: &[Option<FovPort>; 2]) -> [ffi::ovrFovPort; 2] {
+        let max = unsafe { (*self.native()).MaxEyeFov };
+        [requested[0].map_or(max[0], |fov| fov.native()),
+         requested[1].map_or(max[1], |fov| fov.native())]
     }
 
-    /// Enable tracking for this HMD with the specified capabilities.
-    pub fn configure_tracking(&mut self, caps: ffi::ovrTrackingCaps) -> Result<(), OculusError> {
+    fn fov_texture_size(&self, index: usize, fov: &ffi::ovrFovPort, density: f32) -> ffi::ovrSizei {
         unsafe {
-            ovr_invoke!(self.context.invoker().ovrHmd_ConfigureTracking(self.native_hmd, 
-                                                                        caps, 
-                                                                        ffi::ovrTrackingCaps::empty()));
+            self.invoker().ovrHmd_GetFovTextureSize(self.native(), index as i32, *fov, density)
+        }
+    }
+
+    fn fov_texture_sizes(&self, fovs: &[ffi::ovrFovPort; 2], density: f32) -> [ffi::ovrSizei; 2] {
+        [self.fov_texture_size(0, &fovs[0], density), self.fov_texture_size(1, &fovs[1], density)]
+    }
+
+    /// Fails if any of the distortion `caps` isn't supported by this HMD.
+    pub fn check_distortion_caps(&self, caps: ffi::ovrDistortionCaps) -> Result<(), OculusError> {
+        let named_caps = [(ffi::ovrDistortionCap_TimeWarp, "timewarp"),
+                          (ffi::ovrDistortionCap_Vignette, "vignette"),
+                          (ffi::ovrDistortionCap_NoRestore, "no_restore"),
+                          (ffi::ovrDistortionCap_FlipInput, "flip_input"),
+                          (ffi::ovrDistortionCap_SRGB, "srgb"),
+                          (ffi::ovrDistortionCap_Overdrive, "overdrive"),
+                          (ffi::ovrDistortionCap_HqDistortion, "hq_distortion"),
+                          (ffi::ovrDistortionCap_LinuxDevFullscreen, "linux_dev_fullscreen"),
+                          (ffi::ovrDistortionCap_ComputeShader, "compute_shader"),
+                          (ffi::ovrDistortionCap_TimewarpJitDelay, "timewarp_jit_delay"),
+                          (ffi::ovrDistortionCap_ProfileNoSpinWaits, "profile_no_spin_waits")];
+        let supported = unsafe { (*self.native()).DistortionCaps };
+        for &(cap, name) in named_caps.iter() {
+            if caps.contains(cap) && !supported.contains(cap) {
+                return Err(OculusError::UnsupportedRenderOption(name));
+            }
         }
         Ok(())
     }
 
+    /// Fails if `options` asks for mirroring the SDK won't perform: any mirror options in Extend
+    /// mode or with client distortion, where the SDK doesn't mirror.
+    fn check_mirror_options(&self,
+                            options: &RenderOptions,
+                            client_distortion: bool) -> Result<(), OculusError> {
+        let ignored = if client_distortion {
+            Some(("mirror_eye with client distortion", "mirror_destination with client distortion"))
+        } else if !self.is_direct() {
+            Some(("mirror_eye in Extend mode", "mirror_destination in Extend mode"))
+        } else {
+            None
+        };
+        match ignored {
+            Some((eye, _)) if options.mirror_eye.is_some() =>
+                Err(OculusError::UnsupportedRenderOption(eye)),
+            Some((_, destination)) if options.mirror_destination.is_some() =>
+                Err(OculusError::UnsupportedRenderOption(destination)),
+            _ => Ok(())
+        }
+    }
+
     /// Returns true if the HMD is configured to run in Direct mode, or false if it is in Extend
     /// Desktop mode.
     pub fn is_direct(&self) -> bool {
         unsafe {
-            let h = &*self.native_hmd;
+            let h = &*self.native();
             !h.HmdCaps.contains(ffi::ovrHmdCap_ExtendDesktop)
         }
     }
@@ -246,15 +867,55 @@ impl Hmd {
     /// Native resolution of the full HMD display.
     pub fn resolution(&self) -> (u32, u32) {
         unsafe {
-            let ref native_struct = *self.native_hmd;
+            let ref native_struct = *self.native();
             (native_struct.Resolution.w as u32, native_struct.Resolution.h as u32)
         }
     }
 
+    /// Static details about this headset.
+    pub fn descriptor(&self) -> HmdDescriptor {
+        unsafe fn c_string(s: *const u8) -> String {
+            use std::ffi::CStr;
+            if s.is_null() {
+                String::new()
+            } else {
+                String::from_utf8_lossy(CStr::from_ptr(s as *const libc::c_char).to_bytes())
+                    .into_owned()
+            }
+        }
+
+        unsafe {
+            let ref native_struct = *self.native();
+            let serial_len = native_struct.SerialNumber.iter()
+                .position(|c| *c == 0)
+                .unwrap_or(native_struct.SerialNumber.len());
+            HmdDescriptor {
+                hmd_type: HmdType::from_native(native_struct.Type),
+                product_name: c_string(native_struct.ProductName),
+                manufacturer: c_string(native_struct.Manufacturer),
+                vendor_id: native_struct.VendorId as u16,
+                product_id: native_struct.ProductId as u16,
+                serial_number: String::from_utf8_lossy(&native_struct.SerialNumber[..serial_len])
+                    .into_owned(),
+                firmware_version: (native_struct.FirmwareMajor as u16,
+                                   native_struct.FirmwareMinor as u16),
+                camera_frustum: CameraFrustum {
+                    h_fov: native_struct.CameraFrustumHFovInRadians,
+                    v_fov: native_struct.CameraFrustumVFovInRadians,
+                    near_z: native_struct.CameraFrustumNearZInMeters,
+                    far_z: native_struct.CameraFrustumFarZInMeters
+                },
+                hmd_caps: HmdCapabilities::from_native(native_struct.HmdCaps),
+                tracking_caps: TrackingCapabilities::from_native(native_struct.TrackingCaps),
+                resolution: (native_struct.Resolution.w as u32, native_struct.Resolution.h as u32)
+            }
+        }
+    }
+
     /// Get the native display identifier for the monitor represented by this HMD.
     pub fn get_display(&self) -> HmdDisplay {
         unsafe {
-            let ref native_struct = *self.native_hmd;
+            let ref native_struct = *self.native();
             let id = if cfg!(windows) {
                 let s = {
                     use std::ffi::CStr;
@@ -277,22 +938,33 @@ impl Hmd {
     }
 }
 
-impl Drop for Hmd {
-    fn drop(&mut self) {
-        unsafe {
-            self.context.invoker().ovrHmd_Destroy(self.native_hmd);
-        }
-    }
-}
-
 /// An active Oculus rendering context associated with an HMD. Only OpenGL is supported. This
 /// provides access to the basic metadata necessary to prepare OpenGL framebuffers for drawing.
 /// 
-/// See `hmd.render_to()` for details on use.
+/// See `hmd.render_to()` for details on use. A context borrows its `Hmd`, which can't be shared
+/// between threads, so the context is neither `Send` nor `Sync`: it stays on the thread that
+/// created it, which should be the thread that owns the OpenGL context. `begin_frame` lets a
+/// frame's poses visit other threads in the meantime.
 pub struct RenderContext<'a> {
-    eye_texture_sizes: [ffi::ovrSizei; 2],
+    eye_texture_sizes: Cell<[ffi::ovrSizei; 2]>,
     fovs: [ffi::ovrFovPort; 2],
     offsets: [ffi::ovrVector3f; 2],
+    pixels_per_tan_angle: [ffi::ovrVector2f; 2],
+    distortion_caps: ffi::ovrDistortionCaps,
+
+    // true if the application performs distortion itself, rather than the SDK
+    client_distortion: bool,
+    tracking_monitor: Cell<TrackingMonitor>,
+
+    // Index of the next frame to begin. Starts at 1, since the SDK treats 0 as "no index".
+    next_frame_index: Cell<u32>,
+
+    // true while a `Frame` or `FrameToken` is open; shared with tokens so that dropping one on
+    // another thread can close it
+    frame_open: Arc<atomic::AtomicBool>,
+
+    // poses and textures of a token's frame that the SDK has begun but not yet ended
+    render_started: Cell<Option<([ffi::ovrPosef; 2], [ffi::ovrGLTexture; 2])>>,
 
     owning_hmd: &'a Hmd,
 
@@ -335,55 +1007,105 @@ impl GlConfigBuilder {
 
 pub trait CreateRenderContext<'a> {
     fn new(owning_hmd: &'a Hmd,
-           target: &'a RenderTarget) -> Result<Self, OculusError>;
+           target: &'a RenderTarget,
+           options: &RenderOptions) -> Result<Self, OculusError>;
+
+    fn new_client_distortion(owning_hmd: &'a Hmd,
+                             target: &'a RenderTarget,
+                             options: &RenderOptions) -> Result<Self, OculusError>;
 }
 
 impl<'a> CreateRenderContext<'a> for RenderContext<'a> {
     /// Create an active Oculus rendering context.
     fn new(owning_hmd: &'a Hmd, 
-           target: &'a RenderTarget) -> Result<RenderContext<'a>, OculusError> {
+           target: &'a RenderTarget,
+           options: &RenderOptions) -> Result<RenderContext<'a>, OculusError> {
+        let caps = options.distortion_caps;
+        try!(owning_hmd.check_distortion_caps(caps));
+        try!(owning_hmd.check_mirror_options(options, false));
+        let fovs = owning_hmd.eye_fovs(&options.fovs);
         let (w, h) = owning_hmd.resolution();
-        let invoker = owning_hmd.context.invoker();
-        let (offsets, fovs) = unsafe {
+        let invoker = owning_hmd.invoker();
+        let eye_render_desc = unsafe {
             let config = GlConfigBuilder::new(w, h, target.get_multisample() as i32)
                 .native_window(target.get_native_window())
                 .build();
 
-            // TODO: pull in caps as an argument
-            let caps = 
-                ffi::ovrDistortionCap_TimeWarp |
-                ffi::ovrDistortionCap_Overdrive;
             let mut eye_render_desc: [ffi::ovrEyeRenderDesc; 2] = [Default::default(); 2];
-            let hmd_data = &*owning_hmd.native_hmd;
-            ovr_invoke!(invoker.ovrHmd_ConfigureRendering(owning_hmd.native_hmd,
+            ovr_invoke!(invoker.ovrHmd_ConfigureRendering(owning_hmd.native(),
                                                           &config,
                                                           caps,
-                                                          &hmd_data.MaxEyeFov,
+                                                          &fovs,
                                                           &mut eye_render_desc));
-            if owning_hmd.is_direct() {
-                ovr_invoke!(invoker.ovrHmd_AttachToWindow(owning_hmd.native_hmd, 
-                                                          target.get_native_window(), 
-                                                          ptr::null(), 
-                                                          ptr::null()));
-            }
-            ([eye_render_desc[0].HmdToEyeViewOffset, eye_render_desc[1].HmdToEyeViewOffset],
-             [eye_render_desc[0].Fov, eye_render_desc[1].Fov])
+            eye_render_desc
+        };
+        RenderContext::from_render_desc(owning_hmd, target, eye_render_desc, options, caps, false)
+    }
+
+    /// Create a rendering context where the application performs distortion itself.
+    fn new_client_distortion(owning_hmd: &'a Hmd,
+                             target: &'a RenderTarget,
+                             options: &RenderOptions)
+            -> Result<RenderContext<'a>, OculusError> {
+        let mut caps = options.distortion_caps &
+            (ffi::ovrDistortionCap_TimeWarp | ffi::ovrDistortionCap_Vignette);
+        if options.client_vignette {
+            caps.insert(ffi::ovrDistortionCap_Vignette);
+        }
+        try!(owning_hmd.check_distortion_caps(caps));
+        try!(owning_hmd.check_mirror_options(options, true));
+        let fovs = owning_hmd.eye_fovs(&options.fovs);
+        let eye_render_desc = unsafe {
+            let invoker = owning_hmd.invoker();
+            [invoker.ovrHmd_GetRenderDesc(owning_hmd.native(), 0, fovs[0]),
+             invoker.ovrHmd_GetRenderDesc(owning_hmd.native(), 1, fovs[1])]
         };
-        let mut eye_texture_sizes = (0..2).map(|eye_index| {
-            unsafe { 
-                let h = &*owning_hmd.native_hmd;
-                invoker.ovrHmd_GetFovTextureSize(owning_hmd.native_hmd, 
-                                                 eye_index, 
-                                                 h.MaxEyeFov[eye_index as usize], 
-                                                 1f32) 
+        RenderContext::from_render_desc(owning_hmd, target, eye_render_desc, options, caps, true)
+    }
+}
+
+impl<'a> RenderContext<'a> {
+    fn from_render_desc(owning_hmd: &'a Hmd,
+                        target: &'a RenderTarget,
+                        eye_render_desc: [ffi::ovrEyeRenderDesc; 2],
+                        options: &RenderOptions,
+                        distortion_caps: ffi::ovrDistortionCaps,
+                        client_distortion: bool) -> Result<RenderContext<'a>, OculusError> {
+        let invoker = owning_hmd.invoker();
+        if owning_hmd.is_direct() {
+            let (w, h) = owning_hmd.resolution();
+            let source = options.mirror_eye.map(|eye| {
+                let half = Viewport { x: 0, y: 0, width: w / 2, height: h };
+                match eye {
+                    Eye::Left => half.native(),
+                    Eye::Right => Viewport { x: (w / 2) as i32, .. half }.native()
+                }
+            });
+            let destination = options.mirror_destination.map(|viewport| viewport.native());
+            unsafe {
+                ovr_invoke!(invoker.ovrHmd_AttachToWindow(
+                    owning_hmd.native(), 
+                    target.get_native_window(), 
+                    destination.as_ref().map_or(ptr::null(), |rect| rect as *const ffi::ovrRecti),
+                    source.as_ref().map_or(ptr::null(), |rect| rect as *const ffi::ovrRecti)));
             }
-        });
+        }
+        let fovs = [eye_render_desc[0].Fov, eye_render_desc[1].Fov];
 
         Ok(RenderContext {
-            eye_texture_sizes: [eye_texture_sizes.next().unwrap(), 
-                                eye_texture_sizes.next().unwrap()],
+            eye_texture_sizes: Cell::new(owning_hmd.fov_texture_sizes(&fovs,
+                                                                      options.pixel_density)),
             fovs: fovs,
-            offsets: offsets,
+            offsets: [eye_render_desc[0].HmdToEyeViewOffset,
+                      eye_render_desc[1].HmdToEyeViewOffset],
+            pixels_per_tan_angle: [eye_render_desc[0].PixelsPerTanAngleAtCenter,
+                                   eye_render_desc[1].PixelsPerTanAngleAtCenter],
+            distortion_caps: distortion_caps,
+            client_distortion: client_distortion,
+            tracking_monitor: Cell::new(TrackingMonitor::new()),
+            next_frame_index: Cell::new(1),
+            frame_open: Arc::new(atomic::AtomicBool::new(false)),
+            render_started: Cell::new(None),
 
             owning_hmd: owning_hmd,
 
@@ -400,71 +1122,546 @@ impl<'a> RenderContext<'a> {
         self.owning_hmd.dismiss_hsw();
     }
 
-    /// Recenter the headset, using the current orientation and position as the origin.
+    /// Current state of the Health and Safety warning. See `Hmd::hsw_state`.
+    pub fn hsw_state(&self) -> HswState {
+        self.owning_hmd.hsw_state()
+    }
+
+    /// Turn mirroring of headset output to the target window on or off. Only applies in Direct
+    /// mode. Turning mirroring off saves a little rendering time.
+    pub fn set_mirroring(&self, enabled: bool) {
+        self.owning_hmd.set_mirroring(enabled);
+    }
+
+    /// Recenter the headset, using the current orientation and position as the origin. See
+    /// `Hmd::recenter_pose`.
     pub fn recenter_pose(&self) {
         self.owning_hmd.recenter_pose();
     }
 
     /// Return a `(width, height)` tuple containing the suggested size for a render target for the
-    /// given eye.
+    /// given eye, at this context's field of view and pixel density.
     pub fn target_texture_size(&self, eye: &Eye) -> (u32, u32) {
+        let sizes = self.eye_texture_sizes.get();
         let ref size = match eye {
-            &Eye::Left => self.eye_texture_sizes[0],
-            &Eye::Right => self.eye_texture_sizes[1]
+            &Eye::Left => sizes[0],
+            &Eye::Right => sizes[1]
         };
         (size.w as u32, size.h as u32)
     }
 
+    /// Change the pixel density used to size eye textures; see `RenderOptions::pixel_density`.
+    /// Affects `target_texture_size` and bindings created afterwards. Existing bindings keep their
+    /// size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `density` isn't greater than zero.
+    pub fn set_pixel_density(&self, density: f32) {
+        assert!(density > 0f32, "pixel density must be greater than zero");
+        self.eye_texture_sizes.set(self.owning_hmd.fov_texture_sizes(&self.fovs, density));
+    }
+
+    /// Suggested `(width, height)` of a render target for the given eye at an arbitrary field of
+    /// view and pixel density. Rendering with a field of view other than the context's requires
+    /// a new `RenderContext`.
+    pub fn texture_size_for(&self, eye: &Eye, fov: &FovPort, density: f32) -> (u32, u32) {
+        let size = self.owning_hmd.fov_texture_size(eye_index(eye), &fov.native(), density);
+        (size.w as u32, size.h as u32)
+    }
+
     /// Create an appropriate projection matrix for the given eye. This will properly account for
-    /// the native field of view of the associated headset. The returned matrix is a right-handed
+    /// the field of view this context was created with. The returned matrix is a right-handed
     /// projection with an OpenGL clipping range (-w to w).
     pub fn projection_matrix(&self, eye: &Eye, near_z: f32, far_z: f32) -> Matrix4 {     
-        let invoker = self.owning_hmd.context.invoker();
+        self.projection_matrix_with(eye, near_z, far_z, &ProjectionOptions::new())
+    }
+
+    /// Create a projection matrix for the given eye, as `projection_matrix` does, with the
+    /// handedness and depth mapping given by `options`.
+    pub fn projection_matrix_with(&self,
+                                  eye: &Eye,
+                                  near_z: f32,
+                                  far_z: f32,
+                                  options: &ProjectionOptions) -> Matrix4 {
+        let invoker = self.owning_hmd.invoker();
         let matrix = unsafe {
-            let ref fov = match eye {
-                &Eye::Left => self.fovs[0],
-                &Eye::Right => self.fovs[1]
-            };
-            let flags = 
-                ffi::ovrProjection_RightHanded |
-                ffi::ovrProjection_ClipRangeOpenGL;
-            invoker.ovrMatrix4f_Projection(*fov, near_z, far_z, flags)
+            let ref fov = self.fovs[eye_index(eye)];
+            invoker.ovrMatrix4f_Projection(*fov, near_z, far_z, options.modifiers)
         };
-        let ref pm = matrix.M;
-        // ovr matrices are row-major, so we must invert
-        [[pm[0][0], pm[1][0], pm[2][0], pm[3][0]],
-         [pm[0][1], pm[1][1], pm[2][1], pm[3][1]],
-         [pm[0][2], pm[1][2], pm[2][2], pm[3][2]],
-         [pm[0][3], pm[1][3], pm[2][3], pm[3][3]]]
+        matrix4(&matrix)
     }
 
-    /// Create a texture binding given a pair of OpenGL texture IDs for the left and right eye,
-    /// respectively. The left and right textures should be of the size suggested by
-    /// `target_texture_size`.
-    pub fn create_binding(&self, tex_id_left: u32, tex_id_right: u32) -> TextureBinding {
-        TextureBinding::new((self.eye_texture_sizes[0], tex_id_left),
-                            (self.eye_texture_sizes[1], tex_id_right))
+    /// Create an orthographic projection for a head-locked 2D layer, such as a HUD, from the
+    /// eye's `projection` matrix. The layer appears `ortho_distance` meters in front of the
+    /// viewer, with both eyes converging on it. Input coordinates are in pixels from the center of
+    /// the eye's view, where one pixel matches a display pixel at the center of the lens.
+    pub fn ortho_sub_projection(&self,
+                                eye: &Eye,
+                                projection: &Matrix4,
+                                ortho_distance: f32) -> Matrix4 {
+        let index = eye_index(eye);
+        let ref pixels_per_tan_angle = self.pixels_per_tan_angle[index];
+        let scale = ffi::ovrVector2f {
+            x: 1f32 / pixels_per_tan_angle.x,
+            y: 1f32 / pixels_per_tan_angle.y
+        };
+        let matrix = unsafe {
+            self.owning_hmd.invoker().ovrMatrix4f_OrthoSubProjection(native_matrix4(projection),
+                                                                     scale,
+                                                                     ortho_distance,
+                                                                     self.offsets[index].x)
+        };
+        matrix4(&matrix)
     }
-}
 
-impl<'a> Drop for RenderContext<'a> {
-    fn drop(&mut self) {
-        let mut eye_render_desc: [ffi::ovrEyeRenderDesc; 2] = [Default::default(); 2];
-        unsafe {
-            let invoker = self.owning_hmd.context.invoker();
-            let hmd_data = &*self.owning_hmd.native_hmd;
-            ovr_expect!(invoker.ovrHmd_ConfigureRendering(self.owning_hmd.native_hmd,
-                                                          ptr::null(),
-                                                          ffi::ovrDistortionCaps::empty(),
-                                                          &hmd_data.MaxEyeFov,
-                                                          &mut eye_render_desc));
-        }
+    /// Colour to draw in the latency test region of the current frame, or `None` if no latency
+    /// test is running. The DK2 latency tester watches a small region of the display; when the
+    /// runtime performs distortion it draws this colour itself.
+    pub fn latency_test_color(&self) -> Option<[u8; 3]> {
+        self.owning_hmd.latency_test_color()
     }
-}
 
-/// Texture binding, representing a registered pair of OpenGL textures that should serve as render
-/// targets for per-eye viewpoints. Create with `RenderContext::create_binding()`
-pub struct TextureBinding {
+    /// Motion-to-photon latencies most recently measured by the DK2 latency tester, or `None` if
+    /// the headset has no latency tester or hasn't completed a measurement yet.
+    pub fn latency_timings(&self) -> Option<LatencyTimings> {
+        self.owning_hmd.latency_timings()
+    }
+
+    /// True if this context was created for client distortion, where the application performs
+    /// the distortion pass itself.
+    pub fn is_client_distortion(&self) -> bool {
+        self.client_distortion
+    }
+
+    /// Build the distortion mesh for the given eye, matching this context's field of view. The
+    /// mesh only changes if the field of view does, so it can be built once and kept on the GPU.
+    pub fn distortion_mesh(&self, eye: &Eye) -> Result<DistortionMesh, OculusError> {
+        let index = match eye {
+            &Eye::Left => 0,
+            &Eye::Right => 1
+        };
+        let mut native_mesh: ffi::ovrDistortionMesh = Default::default();
+        unsafe {
+            let invoker = self.owning_hmd.invoker();
+            ovr_invoke!(invoker.ovrHmd_CreateDistortionMesh(self.owning_hmd.native(),
+                                                            index as u32,
+                                                            self.fovs[index],
+                                                            self.distortion_caps,
+                                                            &mut native_mesh));
+            let mesh = DistortionMesh::from_native(&native_mesh);
+            invoker.ovrHmd_DestroyDistortionMesh(&mut native_mesh);
+            Ok(mesh)
+        }
+    }
+
+    /// Field of view that the given eye is rendered with.
+    pub fn fov(&self, eye: &Eye) -> FovPort {
+        match eye {
+            &Eye::Left => FovPort::from_native(&self.fovs[0]),
+            &Eye::Right => FovPort::from_native(&self.fovs[1])
+        }
+    }
+
+    /// Index that the next `Frame` on this context will use. Indices increase by one with every
+    /// frame.
+    pub fn next_frame_index(&self) -> u32 {
+        self.next_frame_index.get()
+    }
+
+    /// Fetch eye poses predicted for the frame with index `frame_index`, to begin that frame with
+    /// later using `Frame::with_poses`. Applications that pipeline CPU and GPU work can use this to
+    /// fetch poses for frame N+1 while frame N is still being submitted:
+    ///
+    /// ```no_run
+    /// # extern crate rovr;
+    /// # extern crate libc;
+    /// # use rovr::render::Frame;
+    /// # fn main() {
+    /// # struct EmptyRenderTarget;
+    /// # impl rovr::RenderTarget for EmptyRenderTarget {
+    /// #     fn get_multisample(&self) -> u32 { 0 }
+    /// #     unsafe fn get_native_window(&self) -> *const libc::c_void { std::ptr::null() }
+    /// # }
+    /// # let hmd = rovr::Context::new().unwrap().build_hmd().build().unwrap();
+    /// # let render_target = EmptyRenderTarget;
+    /// # let mut rc = hmd.render_to(&render_target, &rovr::RenderOptions::new()).unwrap();
+    /// # let binding = rc.create_binding(0, 0);
+    /// let frame = Frame::new(&mut rc, &binding).unwrap();
+    /// // draw frame N
+    /// let next_poses = frame.context().fetch_poses(frame.context().next_frame_index());
+    /// // simulate frame N+1 with next_poses, then submit frame N
+    /// frame.submit().unwrap();
+    /// let frame = Frame::with_poses(&mut rc, &binding, next_poses).unwrap();
+    /// # }
+    /// ```
+    pub fn fetch_poses(&self, frame_index: u32) -> FramePoses {
+        let hmd = self.owning_hmd;
+        let mut poses: [ffi::ovrPosef; 2] = [Default::default(); 2];
+        let mut native_state: ffi::ovrTrackingState = Default::default();
+        unsafe {
+            hmd.invoker().ovrHmd_GetEyePoses(hmd.native(),
+                                             frame_index,
+                                             &self.offsets,
+                                             &mut poses,
+                                             &mut native_state);
+        }
+        let mut time = hmd.time_seconds();
+        let space = hmd.tracking_space();
+        let mut tracking_state = space.transform_state(&TrackingState::from_native(&native_state));
+
+        // Recorded poses are already in the tracking space the application saw at the time, and
+        // are reported as-is. The live poses are still the ones submitted to the SDK, so timewarp
+        // only corrects for head motion while the frame is rendered.
+        let replayed = hmd.upcoming_replay_frame();
+        if let Some((_, ref recorded)) = replayed {
+            time = recorded.time;
+            tracking_state = recorded.state;
+        }
+
+        let mut eye_poses = Vec::<FrameEyePose>::with_capacity(2);
+        unsafe {
+            let ref hmd_struct = *hmd.native();
+            for i in hmd_struct.EyeRenderOrder.iter() {
+                let eye = match i {
+                    &0u32 => Eye::Left,
+                    &1u32 => Eye::Right,
+                    _ => panic!("Too many eyes!")
+                };
+                let recorded = replayed.as_ref().and_then(|&(_, ref recorded)| {
+                    recorded.eye_poses.iter().find(|pose| pose.eye == eye).cloned()
+                });
+                eye_poses.push(match recorded {
+                    Some(pose) => pose,
+                    None => {
+                        let ref pose = poses[*i as usize];
+                        space.transform_eye_pose(&FrameEyePose {
+                            eye: eye,
+                            orientation: quaternion(&pose.Orientation),
+                            position: vector3(&pose.Position),
+                            status: tracking_state.status
+                        })
+                    }
+                });
+            }
+        }
+
+        FramePoses {
+            frame_index: frame_index,
+            native_poses: poses,
+            eye_poses: eye_poses,
+            time: time,
+            tracking_state: tracking_state,
+            replay_position: replayed.map(|(position, _)| position)
+        }
+    }
+
+    /// Begin a frame without borrowing this context, for engines that hand frames between
+    /// threads. The returned token is `Send`: it can go to a simulation thread with the frame's
+    /// poses, and come back to this context's thread for drawing. Every SDK call for the frame is
+    /// still made on this context's thread, since the context can't leave it. Call `start_render`
+    /// before drawing, then pass the token to `end_frame` to submit the frame, or `abort_frame` to
+    /// discard it.
+    ///
+    /// This only fetches the frame's poses and predicted timing; the SDK begins the frame in
+    /// `start_render`. Returns an error if a `Frame` or another token is already open on this
+    /// context.
+    ///
+    /// ```no_run
+    /// # extern crate rovr;
+    /// # extern crate libc;
+    /// # use std::thread;
+    /// # fn main() {
+    /// # struct EmptyRenderTarget;
+    /// # impl rovr::RenderTarget for EmptyRenderTarget {
+    /// #     fn get_multisample(&self) -> u32 { 0 }
+    /// #     unsafe fn get_native_window(&self) -> *const libc::c_void { std::ptr::null() }
+    /// # }
+    /// # let hmd = rovr::Context::new().unwrap().build_hmd().build().unwrap();
+    /// # let render_target = EmptyRenderTarget;
+    /// # let rc = hmd.render_to(&render_target, &rovr::RenderOptions::new()).unwrap();
+    /// # let binding = rc.create_binding(0, 0);
+    /// let token = rc.begin_frame(&binding).unwrap();
+    /// let token = thread::spawn(move || {
+    ///     // simulate the frame with token.eye_poses()
+    ///     token
+    /// }).join().unwrap();
+    /// rc.start_render(&token).unwrap();
+    /// // draw, then submit
+    /// rc.end_frame(token).unwrap();
+    /// # }
+    /// ```
+    pub fn begin_frame(&self, texture_binding: &TextureBinding) -> Result<FrameToken, OculusError> {
+        try!(self.claim_frame());
+        self.end_abandoned_frame();
+        let poses = self.fetch_poses(self.next_frame_index());
+        let timing = unsafe {
+            FrameTiming::from_native(&self.owning_hmd.invoker().ovrHmd_GetFrameTiming(
+                self.owning_hmd.native(),
+                poses.frame_index))
+        };
+        let tracking_event = self.advance_frame(&poses);
+        Ok(FrameToken {
+            frame_open: self.frame_open.clone(),
+            textures: texture_binding.textures,
+            poses: poses,
+            timing: timing,
+            tracking_event: tracking_event,
+            ended: false
+        })
+    }
+
+    /// Have the SDK begin a frame begun with `begin_frame`, before drawing it. Call as late as
+    /// possible before drawing. Returns an error if the token belongs to a different context, or
+    /// if rendering has already started for this frame.
+    pub fn start_render(&self, token: &FrameToken) -> Result<(), OculusError> {
+        try!(self.check_token(token));
+        if self.render_started.get().is_some() {
+            return Err(OculusError::FrameError("rendering has already started for this frame"));
+        }
+        unsafe {
+            let invoker = self.owning_hmd.invoker();
+            let hmd = self.owning_hmd.native();
+            if self.client_distortion {
+                invoker.ovrHmd_BeginFrameTiming(hmd, token.poses.frame_index);
+            } else {
+                invoker.ovrHmd_BeginFrame(hmd, token.poses.frame_index);
+            }
+        }
+        self.render_started.set(Some((token.poses.native_poses, token.textures)));
+        Ok(())
+    }
+
+    /// Finish a frame begun with `begin_frame` and hand it to the SDK, as `Frame::submit` does.
+    /// Returns an error if the token belongs to a different context, if `start_render` wasn't
+    /// called for it, or if the SDK reported an error while ending the frame.
+    pub fn end_frame(&self, mut token: FrameToken) -> Result<SubmitInfo, OculusError> {
+        try!(self.check_token(&token));
+        if self.render_started.take().is_none() {
+            return Err(OculusError::FrameError("start_render must be called before end_frame"));
+        }
+        let error = self.end_native_frame(token.poses.native_poses, token.textures);
+        token.ended = true;
+        self.frame_open.store(false, atomic::Ordering::SeqCst);
+        self.submit_info(&token.poses, error)
+    }
+
+    /// Finish a frame begun with `begin_frame` without submitting it. If `start_render` was
+    /// called, the SDK ends the frame as `Frame::abort` does; otherwise nothing is handed to the
+    /// SDK. The frame's index is still used up. Returns an error if the token belongs to a
+    /// different context.
+    pub fn abort_frame(&self, mut token: FrameToken) -> Result<(), OculusError> {
+        try!(self.check_token(&token));
+        if let Some(started) = self.render_started.take() {
+            self.abort_native_frame(started);
+        }
+        token.ended = true;
+        self.frame_open.store(false, atomic::Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Start and end timewarp matrices for a frame begun with `begin_frame`, as
+    /// `Frame::timewarp_matrices` returns them. Call after `start_render`. Returns an error if
+    /// the token belongs to a different context.
+    pub fn timewarp_matrices(&self,
+                             token: &FrameToken,
+                             eye: &Eye) -> Result<(Matrix4, Matrix4), OculusError> {
+        try!(self.check_token(token));
+        Ok(self.eye_timewarp_matrices(eye, &token.poses.native_poses))
+    }
+
+    /// Scale and offset for a frame begun with `begin_frame`, as `Frame::uv_scale_offset`
+    /// returns them. Returns an error if the token belongs to a different context.
+    pub fn uv_scale_offset(&self,
+                           token: &FrameToken,
+                           eye: &Eye) -> Result<([f32; 2], [f32; 2]), OculusError> {
+        try!(self.check_token(token));
+        Ok(self.eye_uv_scale_offset(eye, &token.textures))
+    }
+
+    fn check_token(&self, token: &FrameToken) -> Result<(), OculusError> {
+        let own = &*self.frame_open as *const atomic::AtomicBool;
+        if &*token.frame_open as *const atomic::AtomicBool != own {
+            return Err(OculusError::FrameError("frame token belongs to a different RenderContext"));
+        }
+        Ok(())
+    }
+
+    // Mark a frame as open. Fails if one already is.
+    fn claim_frame(&self) -> Result<(), OculusError> {
+        if self.frame_open.swap(true, atomic::Ordering::SeqCst) {
+            return Err(OculusError::FrameError("a frame is already open on this RenderContext"));
+        }
+        Ok(())
+    }
+
+    // End a frame the SDK began for a token that was then dropped, rather than ended or aborted.
+    fn end_abandoned_frame(&self) {
+        if let Some(started) = self.render_started.take() {
+            self.abort_native_frame(started);
+        }
+    }
+
+    // Move the next frame index, and any replay the poses came from, past a newly opened frame,
+    // and return any tracking event since the previous frame.
+    fn advance_frame(&self, poses: &FramePoses) -> Option<TrackingEvent> {
+        let next_index = self.next_frame_index.get();
+        self.next_frame_index.set(cmp::max(next_index, poses.frame_index + 1));
+        if let Some(position) = poses.replay_position {
+            self.owning_hmd.advance_replay(position);
+        }
+
+        let mut monitor = self.tracking_monitor.get();
+        let tracking_event = monitor.update(&poses.tracking_state.status);
+        self.tracking_monitor.set(monitor);
+        tracking_event
+    }
+
+    // End the frame the SDK began last: with ovrHmd_EndFrame and the given poses and textures,
+    // or with ovrHmd_EndFrameTiming for client distortion. Returns the error the SDK reported
+    // while ending it, if any.
+    fn end_native_frame(&self, poses: [ffi::ovrPosef; 2], textures: [ffi::ovrGLTexture; 2])
+        -> Option<String> {
+        let hmd = self.owning_hmd;
+
+        // drain any earlier error, so that only one raised by ending this frame is reported
+        hmd.last_error();
+        unsafe {
+            let invoker = hmd.invoker();
+            if self.client_distortion {
+                invoker.ovrHmd_EndFrameTiming(hmd.native());
+            } else {
+                invoker.ovrHmd_EndFrame(hmd.native(), &poses, &textures);
+            }
+        }
+        hmd.last_error()
+    }
+
+    // End the frame the SDK began last without presenting any of its drawing; see `Frame::abort`.
+    fn abort_native_frame(&self, own: ([ffi::ovrPosef; 2], [ffi::ovrGLTexture; 2])) {
+        // texture 0 is OpenGL's default texture, which has no image and so samples as black
+        let (poses, mut textures) = own;
+        for texture in textures.iter_mut() {
+            texture.TexId = 0;
+        }
+        self.end_native_frame(poses, textures);
+    }
+
+    fn eye_timewarp_matrices(&self,
+                             eye: &Eye,
+                             native_poses: &[ffi::ovrPosef; 2]) -> (Matrix4, Matrix4) {
+        let index = eye_index(eye);
+        let mut matrices: [ffi::ovrMatrix4f; 2] = [Default::default(); 2];
+        unsafe {
+            self.owning_hmd.invoker().ovrHmd_GetEyeTimewarpMatrices(self.owning_hmd.native(),
+                                                                    index as u32,
+                                                                    native_poses[index],
+                                                                    &mut matrices);
+        }
+        (matrix4(&matrices[0]), matrix4(&matrices[1]))
+    }
+
+    fn eye_uv_scale_offset(&self,
+                           eye: &Eye,
+                           textures: &[ffi::ovrGLTexture; 2]) -> ([f32; 2], [f32; 2]) {
+        let index = eye_index(eye);
+        let ref texture = textures[index];
+        let mut scale_offset: [ffi::ovrVector2f; 2] = [Default::default(); 2];
+        unsafe {
+            self.owning_hmd.invoker().ovrHmd_GetRenderScaleAndOffset(self.fovs[index],
+                                                                     texture.TextureSize,
+                                                                     texture.RenderViewport,
+                                                                     &mut scale_offset);
+        }
+        ([scale_offset[0].x, scale_offset[0].y], [scale_offset[1].x, scale_offset[1].y])
+    }
+
+    fn submit_info(&self, poses: &FramePoses, error: Option<String>)
+        -> Result<SubmitInfo, OculusError> {
+        match error {
+            Some(error) => Err(OculusError::SubmitError(error)),
+            None => Ok(SubmitInfo {
+                frame_index: poses.frame_index,
+                begin_time: poses.time,
+                submit_time: self.owning_hmd.time_seconds()
+            })
+        }
+    }
+
+    /// Create a texture binding given a pair of OpenGL texture IDs for the left and right eye,
+    /// respectively. The left and right textures should be of the size suggested by
+    /// `target_texture_size`.
+    pub fn create_binding(&self, tex_id_left: u32, tex_id_right: u32) -> TextureBinding {
+        let sizes = self.eye_texture_sizes.get();
+        TextureBinding::new((sizes[0], tex_id_left),
+                            (sizes[1], tex_id_right))
+    }
+
+    /// Return a `(width, height)` tuple containing the suggested size for a single texture
+    /// holding both eyes side by side, as used by `create_shared_binding`.
+    pub fn shared_texture_size(&self) -> (u32, u32) {
+        let sizes = self.eye_texture_sizes.get();
+        ((sizes[0].w + sizes[1].w) as u32, cmp::max(sizes[0].h, sizes[1].h) as u32)
+    }
+
+    /// Create a texture binding for a single OpenGL texture holding both eyes side by side, left
+    /// eye first. The texture should be of the size suggested by `shared_texture_size`.
+    pub fn create_shared_binding(&self, tex_id: u32) -> TextureBinding {
+        let sizes = self.eye_texture_sizes.get();
+        let (w, h) = self.shared_texture_size();
+        let left = Viewport { x: 0, y: 0, width: sizes[0].w as u32, height: sizes[0].h as u32 };
+        let right = Viewport {
+            x: sizes[0].w,
+            y: 0,
+            width: sizes[1].w as u32,
+            height: sizes[1].h as u32
+        };
+        TextureBinding::shared(tex_id, (w, h), [left, right])
+    }
+
+    /// Create a texture binding for a single OpenGL texture of size `texture_size`, with each
+    /// eye rendered to its own region of it. `viewports` is indexed left eye first.
+    ///
+    /// # Failure
+    ///
+    /// Fails with `Err(OculusError::InvalidInput)` if either viewport doesn't lie within the
+    /// texture.
+    pub fn create_shared_binding_with_viewports(&self,
+                                                tex_id: u32,
+                                                texture_size: (u32, u32),
+                                                viewports: [Viewport; 2])
+        -> Result<TextureBinding, OculusError> {
+        if !viewports[0].fits(texture_size) || !viewports[1].fits(texture_size) {
+            return Err(OculusError::InvalidInput("viewport must lie within the shared texture"));
+        }
+        Ok(TextureBinding::shared(tex_id, texture_size, viewports))
+    }
+}
+
+impl<'a> Drop for RenderContext<'a> {
+    fn drop(&mut self) {
+        self.end_abandoned_frame();
+        if self.client_distortion {
+            return;
+        }
+        let mut eye_render_desc: [ffi::ovrEyeRenderDesc; 2] = [Default::default(); 2];
+        unsafe {
+            let invoker = self.owning_hmd.invoker();
+            let hmd_data = &*self.owning_hmd.native();
+            ovr_expect!(invoker.ovrHmd_ConfigureRendering(self.owning_hmd.native(),
+                                                          ptr::null(),
+                                                          ffi::ovrDistortionCaps::empty(),
+                                                          &hmd_data.MaxEyeFov,
+                                                          &mut eye_render_desc));
+        }
+    }
+}
+
+/// Texture binding, representing a registered pair of OpenGL textures that should serve as render
+/// targets for per-eye viewpoints. Create with `RenderContext::create_binding()`, or
+/// `RenderContext::create_shared_binding()` to render both eyes to one texture. When sharing a
+/// texture, render each eye to its `viewport`.
+pub struct TextureBinding {
     textures: [ffi::ovrGLTexture; 2]
 }
 
@@ -490,81 +1687,894 @@ impl TextureBinding {
         }
     }
 
+    fn shared(id: u32, size: (u32, u32), viewports: [Viewport; 2]) -> TextureBinding {
+        let texture_struct = |viewport: &Viewport| ffi::ovrGLTexture {
+            API: ffi::ovrRenderAPI_OpenGL,
+            TextureSize: ffi::ovrSizei { w: size.0 as i32, h: size.1 as i32 },
+            RenderViewport: viewport.native(),
+            TexId: id,
+            .. Default::default()
+        };
+
+        TextureBinding {
+            textures: [texture_struct(&viewports[0]), texture_struct(&viewports[1])]
+        }
+    }
+
+    /// `(width, height)` of the given eye's texture.
+    pub fn texture_size(&self, eye: &Eye) -> (u32, u32) {
+        let ref size = self.texture(eye).TextureSize;
+        (size.w as u32, size.h as u32)
+    }
+
+    /// Region of the given eye's texture that is rendered to.
+    pub fn viewport(&self, eye: &Eye) -> Viewport {
+        Viewport::from_native(&self.texture(eye).RenderViewport)
+    }
+
+    fn texture(&self, eye: &Eye) -> &ffi::ovrGLTexture {
+        match eye {
+            &Eye::Left => &self.textures[0],
+            &Eye::Right => &self.textures[1]
+        }
+    }
+}
+
+/// A rectangular region of a texture, in pixels from the top left.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Viewport {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32
+}
+
+impl Viewport {
+    fn from_native(rect: &ffi::ovrRecti) -> Viewport {
+        Viewport {
+            x: rect.Pos.x,
+            y: rect.Pos.y,
+            width: rect.Size.w as u32,
+            height: rect.Size.h as u32
+        }
+    }
+
+    /// True if this viewport lies within a texture of the given `(width, height)`, which must
+    /// also be addressable by the SDK.
+    fn fits(&self, texture_size: (u32, u32)) -> bool {
+        // compare in i64, so that large widths and heights can't overflow
+        let max = i32::max_value() as i64;
+        let (w, h) = (texture_size.0 as i64, texture_size.1 as i64);
+        w <= max && h <= max && self.x >= 0 && self.y >= 0 &&
+            self.x as i64 + self.width as i64 <= w &&
+            self.y as i64 + self.height as i64 <= h
+    }
+
+    fn native(&self) -> ffi::ovrRecti {
+        ffi::ovrRecti {
+            Pos: ffi::ovrVector2i { x: self.x, y: self.y },
+            Size: ffi::ovrSizei { w: self.width as i32, h: self.height as i32 }
+        }
+    }
+}
+
+/// A field of view, as the tangents of the angles from the view axis to each edge.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FovPort {
+    pub up_tan: f32,
+    pub down_tan: f32,
+    pub left_tan: f32,
+    pub right_tan: f32
+}
+
+impl FovPort {
+    fn from_native(fov: &ffi::ovrFovPort) -> FovPort {
+        FovPort {
+            up_tan: fov.UpTan,
+            down_tan: fov.DownTan,
+            left_tan: fov.LeftTan,
+            right_tan: fov.RightTan
+        }
+    }
+
+    fn native(&self) -> ffi::ovrFovPort {
+        ffi::ovrFovPort {
+            UpTan: self.up_tan,
+            DownTan: self.down_tan,
+            LeftTan: self.left_tan,
+            RightTan: self.right_tan
+        }
+    }
+}
+
+/// State of the Health and Safety warning shown by the runtime when rendering starts. Times are
+/// in seconds on the SDK clock.
+///
+/// ```
+/// use rovr::HswState;
+///
+/// let state = HswState { displayed: true, start_time: 10f64, dismissible_time: 15f64 };
+/// assert!(!state.is_dismissible(12f64));
+/// assert!(state.is_dismissible(15f64));
+/// assert!(!HswState::default().is_dismissible(15f64));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HswState {
+    /// True while the warning is being displayed.
+    pub displayed: bool,
+
+    /// Time the warning was first displayed.
+    pub start_time: f64,
+
+    /// Earliest time the warning can be dismissed.
+    pub dismissible_time: f64
+}
+
+impl HswState {
+    /// True if the warning is displayed and can be dismissed at time `now`.
+    pub fn is_dismissible(&self, now: f64) -> bool {
+        self.displayed && now >= self.dismissible_time
+    }
+}
+
+/// Latencies measured by the DK2 latency tester, in seconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LatencyTimings {
+    /// Time from the start of rendering to photons on the display.
+    pub render: f32,
+
+    /// Time from the timewarp point to photons on the display.
+    pub timewarp: f32,
+
+    /// Time from the end of distortion to photons on the display.
+    pub post_present: f32,
+
+    /// Difference between the predicted and measured render latency.
+    pub render_error: f32,
+
+    /// Difference between the predicted and measured timewarp latency.
+    pub timewarp_error: f32
+}
+
+/// An orientation and position in tracking space.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pose {
+    pub orientation: Quaternion,
+    pub position: Vector3
+}
+
+impl Pose {
+    fn from_native(pose: &ffi::ovrPosef) -> Pose {
+        Pose {
+            orientation: quaternion(&pose.Orientation),
+            position: vector3(&pose.Position)
+        }
+    }
+}
+
+/// A pose along with its first and second derivatives. Angular values are in radians, linear
+/// values in meters.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PoseState {
+    pub pose: Pose,
+    pub angular_velocity: Vector3,
+    pub linear_velocity: Vector3,
+    pub angular_acceleration: Vector3,
+    pub linear_acceleration: Vector3,
+
+    /// Absolute time this pose refers to, in seconds on the SDK clock.
+    pub time: f64
+}
+
+impl PoseState {
+    fn from_native(state: &ffi::ovrPoseStatef) -> PoseState {
+        PoseState {
+            pose: Pose::from_native(&state.ThePose),
+            angular_velocity: vector3(&state.AngularVelocity),
+            linear_velocity: vector3(&state.LinearVelocity),
+            angular_acceleration: vector3(&state.AngularAcceleration),
+            linear_acceleration: vector3(&state.LinearAcceleration),
+            time: state.TimeInSeconds
+        }
+    }
+}
+
+/// Which parts of the tracking system were working when a tracking state was captured.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrackingStatus {
+    /// Head orientation is being tracked.
+    pub orientation_tracked: bool,
+
+    /// Head position is being tracked. If false, the reported position is a model-based
+    /// estimate, and will drift.
+    pub position_tracked: bool,
+
+    /// The camera pose is known.
+    pub camera_pose_tracked: bool,
+
+    /// The positional tracking camera is connected.
+    pub position_connected: bool,
+
+    /// The headset is connected.
+    pub hmd_connected: bool
+}
+
+impl TrackingStatus {
+    fn from_native(flags: ffi::ovrStatusBits) -> TrackingStatus {
+        TrackingStatus {
+            orientation_tracked: flags.contains(ffi::ovrStatus_OrientationTracked),
+            position_tracked: flags.contains(ffi::ovrStatus_PositionTracked),
+            camera_pose_tracked: flags.contains(ffi::ovrStatus_CameraPoseTracked),
+            position_connected: flags.contains(ffi::ovrStatus_PositionConnected),
+            hmd_connected: flags.contains(ffi::ovrStatus_HmdConnected)
+        }
+    }
+}
+
+/// A change in tracking status between two consecutive observations.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TrackingEvent {
+    /// Positional tracking was lost, e.g. because the headset left the camera's view. Reported
+    /// positions will be estimates until tracking is regained.
+    PositionLost,
+
+    /// Positional tracking was regained.
+    PositionRegained
+}
+
+/// Edge detector over a sequence of `TrackingStatus` observations. Feed it the status from each
+/// frame or tracking state query, and it reports when positional tracking is lost or regained.
+/// The first observation establishes a baseline and never produces an event.
+///
+/// ```
+/// use rovr::tracking::{TrackingEvent, TrackingMonitor, TrackingStatus};
+///
+/// let tracked = TrackingStatus { position_tracked: true, .. Default::default() };
+/// let untracked = TrackingStatus::default();
+///
+/// let mut monitor = TrackingMonitor::new();
+/// assert_eq!(monitor.update(&tracked), None);
+/// assert_eq!(monitor.update(&untracked), Some(TrackingEvent::PositionLost));
+/// assert_eq!(monitor.update(&untracked), None);
+/// assert_eq!(monitor.update(&tracked), Some(TrackingEvent::PositionRegained));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TrackingMonitor {
+    last: Option<TrackingStatus>
+}
+
+impl TrackingMonitor {
+    pub fn new() -> TrackingMonitor {
+        TrackingMonitor { last: None }
+    }
+
+    /// Record a new status observation, returning an event if positional tracking changed since
+    /// the previous observation.
+    pub fn update(&mut self, status: &TrackingStatus) -> Option<TrackingEvent> {
+        let event = match self.last {
+            Some(ref last) if last.position_tracked && !status.position_tracked =>
+                Some(TrackingEvent::PositionLost),
+            Some(ref last) if !last.position_tracked && status.position_tracked =>
+                Some(TrackingEvent::PositionRegained),
+            _ => None
+        };
+        self.last = Some(*status);
+        event
+    }
+}
+
+/// A raw sample from the headset's inertial measurement unit, in the headset's local frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SensorData {
+    /// Acceleration reading, in meters per second squared.
+    pub accelerometer: Vector3,
+
+    /// Rotation rate, in radians per second.
+    pub gyro: Vector3,
+
+    /// Magnetic field, in gauss.
+    pub magnetometer: Vector3,
+
+    /// Sensor temperature, in degrees Celsius.
+    pub temperature: f32,
+
+    /// Time the sample was taken, in seconds on the SDK clock.
+    pub time: f64
+}
+
+impl SensorData {
+    fn from_native(data: &ffi::ovrSensorData) -> SensorData {
+        SensorData {
+            accelerometer: vector3(&data.Accelerometer),
+            gyro: vector3(&data.Gyro),
+            magnetometer: vector3(&data.Magnetometer),
+            temperature: data.Temperature,
+            time: data.TimeInSeconds as f64
+        }
+    }
+}
+
+/// Full tracking state of the headset at a point in time.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrackingState {
+    /// Head pose, including velocity and acceleration.
+    pub head: PoseState,
+
+    /// Pose of the positional tracking camera.
+    pub camera_pose: Pose,
+
+    /// Pose of the positional tracking camera, with roll and pitch removed so it is level with
+    /// the ground.
+    pub leveled_camera_pose: Pose,
+
+    /// Most recent raw IMU sample. This is not affected by prediction.
+    pub raw_sensor: SensorData,
+
+    /// Status of the tracking system.
+    pub status: TrackingStatus
+}
+
+impl TrackingState {
+    fn from_native(state: &ffi::ovrTrackingState) -> TrackingState {
+        TrackingState {
+            head: PoseState::from_native(&state.HeadPose),
+            camera_pose: Pose::from_native(&state.CameraPose),
+            leveled_camera_pose: Pose::from_native(&state.LeveledCameraPose),
+            raw_sensor: SensorData::from_native(&state.RawSensorData),
+            status: TrackingStatus::from_native(state.StatusFlags)
+        }
+    }
+}
+
+/// Head and eye poses predicted for a particular time.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PredictedPose {
+    /// Full predicted tracking state, including the head pose.
+    pub state: TrackingState,
+
+    /// Predicted eye poses, indexed with the left eye first.
+    pub eye_poses: [FrameEyePose; 2]
+}
+
+/// Iterator over raw IMU samples, polled from the headset's tracking state.
+///
+/// The stream is lossy. The IMU samples at around 1000Hz, but only the most recent sample is ever
+/// available, so samples that arrive between two polls are never seen. Use `SensorData::time` to
+/// detect gaps. A sample is reported only if it's newer than the previous one reported.
+///
+/// `next()` blocks until a new sample arrives, and ends the iteration if none arrives within the
+/// timeout given to `Hmd::sensor_samples`. `try_next()` never blocks.
+pub struct SensorSamples<'a> {
+    hmd: &'a Hmd,
+    timeout: Duration,
+    last_time: Option<f64>
+}
+
+impl<'a> SensorSamples<'a> {
+    pub fn new(hmd: &'a Hmd, timeout: Duration) -> SensorSamples<'a> {
+        SensorSamples {
+            hmd: hmd,
+            timeout: timeout,
+            last_time: None
+        }
+    }
+
+    /// The latest sample, if it's newer than the previous one returned. Doesn't block.
+    pub fn try_next(&mut self) -> Option<SensorData> {
+        let sample = self.hmd.tracking_state(0f64).raw_sensor;
+        let is_new = match self.last_time {
+            Some(last) => sample.time > last,
+            None => true
+        };
+        if is_new {
+            self.last_time = Some(sample.time);
+            Some(sample)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> Iterator for SensorSamples<'a> {
+    type Item = SensorData;
+
+    fn next(&mut self) -> Option<SensorData> {
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            if let Some(sample) = self.try_next() {
+                return Some(sample);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            thread::sleep(cmp::min(Duration::from_millis(1), deadline - now));
+        }
+    }
 }
 
 /// A single eye's pose for a frame.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FrameEyePose {
     pub eye: Eye,
     pub orientation: Quaternion,
     pub position: Vector3,
+
+    /// Tracking status at the time the pose was captured. If `status.position_tracked` is false,
+    /// `position` is an estimate and should not be trusted.
+    pub status: TrackingStatus
+}
+
+/// Timing information for a frame, as predicted by the SDK when the frame began. Absolute times
+/// are in seconds on the SDK clock.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FrameTiming {
+    /// Time between the start of the previous frame and this one. Advance animation by this
+    /// amount to keep it in step with the display.
+    pub delta_seconds: f32,
+
+    /// Absolute time this frame began.
+    pub this_frame_seconds: f64,
+
+    /// Absolute time at which timewarp will sample tracking for this frame.
+    pub timewarp_point_seconds: f64,
+
+    /// Absolute time the next frame is expected to begin.
+    pub next_frame_seconds: f64,
+
+    /// Absolute time at which this frame will be halfway through scanout. Head poses for the frame
+    /// are predicted for this time.
+    pub scanout_midpoint_seconds: f64,
+
+    /// Absolute time at which each eye's image will be scanned out, indexed by `Eye` (left first).
+    pub eye_scanout_seconds: [f64; 2]
+}
+
+impl FrameTiming {
+    fn from_native(timing: &ffi::ovrFrameTiming) -> FrameTiming {
+        FrameTiming {
+            delta_seconds: timing.DeltaSeconds,
+            this_frame_seconds: timing.ThisFrameSeconds,
+            timewarp_point_seconds: timing.TimewarpPointSeconds,
+            next_frame_seconds: timing.NextFrameSeconds,
+            scanout_midpoint_seconds: timing.ScanoutMidpointSeconds,
+            eye_scanout_seconds: timing.EyeScanoutSeconds
+        }
+    }
+}
+
+/// A vertex of a distortion mesh. See the `render` module documentation for how to use these in a
+/// distortion shader.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DistortionVertex {
+    /// Position on the display, in normalized device coordinates over the whole framebuffer.
+    pub screen_position: [f32; 2],
+
+    /// Blend factor between the start and end timewarp matrices.
+    pub timewarp_factor: f32,
+
+    /// Brightness factor for fading out the edge of the lens, from 0 (black) to 1.
+    pub vignette_factor: f32,
+
+    /// Tangents of the eye angles to sample for the red, green and blue channels. These differ to
+    /// correct for chromatic aberration.
+    pub tan_eye_angles_r: [f32; 2],
+    pub tan_eye_angles_g: [f32; 2],
+    pub tan_eye_angles_b: [f32; 2]
+}
+
+/// Triangle mesh covering one eye's half of the display, for performing distortion. Indices form a
+/// triangle list.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DistortionMesh {
+    pub vertices: Vec<DistortionVertex>,
+    pub indices: Vec<u16>
+}
+
+impl DistortionMesh {
+    unsafe fn from_native(mesh: &ffi::ovrDistortionMesh) -> DistortionMesh {
+        use std::slice;
+        fn vector2(v: &ffi::ovrVector2f) -> [f32; 2] {
+            [v.x, v.y]
+        }
+
+        let vertices = slice::from_raw_parts(mesh.pVertexData, mesh.VertexCount as usize);
+        let indices = slice::from_raw_parts(mesh.pIndexData, mesh.IndexCount as usize);
+        DistortionMesh {
+            vertices: vertices.iter().map(|v| DistortionVertex {
+                screen_position: vector2(&v.ScreenPosNDC),
+                timewarp_factor: v.TimeWarpFactor,
+                vignette_factor: v.VignetteFactor,
+                tan_eye_angles_r: vector2(&v.TanEyeAnglesR),
+                tan_eye_angles_g: vector2(&v.TanEyeAnglesG),
+                tan_eye_angles_b: vector2(&v.TanEyeAnglesB)
+            }).collect(),
+            indices: indices.to_vec()
+        }
+    }
+}
+
+/// Eye poses and tracking state predicted for a particular frame. Fetch with
+/// `RenderContext::fetch_poses`.
+pub struct FramePoses {
+    frame_index: u32,
+
+    // in native tracking space, as passed to ovrHmd_EndFrame
+    native_poses: [ffi::ovrPosef; 2],
+
+    // in the application's tracking space, in render order
+    eye_poses: Vec<FrameEyePose>,
+    time: f64,
+    tracking_state: TrackingState,
+
+    // position of the recorded frame these poses came from, if replaying
+    replay_position: Option<usize>
+}
+
+impl FramePoses {
+    /// Index of the frame these poses were predicted for.
+    pub fn frame_index(&self) -> u32 {
+        self.frame_index
+    }
+
+    /// Eye poses, in the suggested rendering order.
+    pub fn eye_poses(&self) -> vec::IntoIter<FrameEyePose> {
+        self.eye_poses.clone().into_iter()
+    }
+
+    /// Time the poses were fetched, in seconds on the SDK clock.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Tracking state sampled along with the eye poses, in the headset's tracking space at the
+    /// time of fetching.
+    pub fn tracking_state(&self) -> TrackingState {
+        self.tracking_state
+    }
+}
+
+/// Summary of a submitted frame, returned by `Frame::submit`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SubmitInfo {
+    /// Index of the submitted frame.
+    pub frame_index: u32,
+
+    /// Time the frame began, in seconds on the SDK clock.
+    pub begin_time: f64,
+
+    /// Time submission completed, in seconds on the SDK clock.
+    pub submit_time: f64
 }
 
 /// A single frame. All OpenGL rendering to both eyes' frame buffers should happen while this
-/// object is alive. When going out of scope, the Oculus SDK will complete the rendering process,
-/// including post-processing and any necessary buffer swapping.
+/// object is alive. Finish the frame with `submit`, which completes the rendering process
+/// (including post-processing and any necessary buffer swapping), or `abort`.
+///
+/// A frame holds a mutable borrow of its `RenderContext`, so only one frame can be open at a
+/// time; use `context` to reach the context while drawing. A frame that goes out of scope without
+/// being finished is submitted as a fallback, ignoring any error, unless the thread is panicking,
+/// in which case it's aborted as `abort` does.
+///
+/// With client distortion, the application completes the rendering process itself and should
+/// submit the frame after presenting it.
 pub struct Frame<'a> {
     owning_context: &'a RenderContext<'a>,
-    textures: &'a TextureBinding,
-    poses: [ffi::ovrPosef; 2]
+
+    // copied from the binding, so that viewports can be changed for this frame only
+    textures: [ffi::ovrGLTexture; 2],
+    binding: &'a TextureBinding,
+    poses: FramePoses,
+    timing: FrameTiming,
+    tracking_event: Option<TrackingEvent>,
+
+    // true once submitted or aborted
+    finished: bool
 }
 
 impl<'a> Frame<'a> {
-    /// Start a frame.
-    pub fn new(owning_context: &'a RenderContext, 
-               texture_binding: &'a TextureBinding) -> Frame<'a> {
-        let mut poses: [ffi::ovrPosef; 2] = [Default::default(); 2];
-        let invoker = owning_context.owning_hmd.context.invoker();
-        unsafe {
-            invoker.ovrHmd_BeginFrame(owning_context.owning_hmd.native_hmd, 0);
-            invoker.ovrHmd_GetEyePoses(owning_context.owning_hmd.native_hmd,
-                                       0,
-                                       &owning_context.offsets,
-                                       &mut poses,
-                                       ptr::null_mut());
+    /// Start a frame, using the context's next frame index. Returns an error if a `FrameToken` is
+    /// open on the context.
+    pub fn new<'b: 'a>(owning_context: &'a mut RenderContext<'b>,
+                       texture_binding: &'a TextureBinding) -> Result<Frame<'a>, OculusError> {
+        let owning_context: &'a RenderContext<'a> = owning_context;
+        try!(owning_context.claim_frame());
+        let poses = owning_context.fetch_poses(owning_context.next_frame_index());
+        Ok(Frame::begin(owning_context, texture_binding, poses))
+    }
+
+    /// Start a frame using poses fetched earlier with `RenderContext::fetch_poses`. The frame
+    /// takes the index the poses were fetched for; the context's next frame index continues from
+    /// there.
+    ///
+    /// Returns an error if a `FrameToken` is open on the context, or if the poses were fetched for
+    /// a frame index that has already been used, such as poses fetched before the frame they were
+    /// meant for was begun by other means.
+    pub fn with_poses<'b: 'a>(owning_context: &'a mut RenderContext<'b>,
+                              texture_binding: &'a TextureBinding,
+                              poses: FramePoses) -> Result<Frame<'a>, OculusError> {
+        let owning_context: &'a RenderContext<'a> = owning_context;
+        if poses.frame_index < owning_context.next_frame_index() {
+            return Err(OculusError::FrameError(
+                "poses were fetched for a frame index that has already been used"));
         }
+        try!(owning_context.claim_frame());
+        Ok(Frame::begin(owning_context, texture_binding, poses))
+    }
+
+    // Begin a frame in the SDK, once the context's open flag has been claimed for it.
+    fn begin(owning_context: &'a RenderContext<'a>,
+             texture_binding: &'a TextureBinding,
+             poses: FramePoses) -> Frame<'a> {
+        owning_context.end_abandoned_frame();
+        let timing = unsafe {
+            let invoker = owning_context.owning_hmd.invoker();
+            let hmd = owning_context.owning_hmd.native();
+            FrameTiming::from_native(&if owning_context.client_distortion {
+                invoker.ovrHmd_BeginFrameTiming(hmd, poses.frame_index)
+            } else {
+                invoker.ovrHmd_BeginFrame(hmd, poses.frame_index)
+            })
+        };
+        let tracking_event = owning_context.advance_frame(&poses);
 
         Frame {
             owning_context: owning_context,
-            textures: texture_binding,
-            poses: poses
+            textures: texture_binding.textures,
+            binding: texture_binding,
+            poses: poses,
+            timing: timing,
+            tracking_event: tracking_event,
+            finished: false
         }
     }
 
+    /// The `RenderContext` this frame belongs to.
+    pub fn context(&self) -> &RenderContext<'a> {
+        self.owning_context
+    }
+
+    /// Index of this frame on its `RenderContext`.
+    pub fn index(&self) -> u32 {
+        self.poses.frame_index
+    }
+
     /// Get an iterable list of eye poses that should be drawn for this frame. These are returned
     /// in the suggested rendering order.
     pub fn eye_poses(&self) -> vec::IntoIter<FrameEyePose> {
-        unsafe {
-            let ref hmd_struct = *self.owning_context.owning_hmd.native_hmd;
-            let mut poses = Vec::<FrameEyePose>::with_capacity(2);
-            for i in hmd_struct.EyeRenderOrder.iter() {
-                let eye = match i {
-                    &0u32 => Eye::Left,
-                    &1u32 => Eye::Right,
-                    _ => panic!("Too many eyes!")
-                };
-                let position = self.poses[*i as usize].Position;
-                let orientation = self.poses[*i as usize].Orientation;
-
-                // note that we must invert projection_matrix to column major
-                poses.push(FrameEyePose {
-                    eye: eye,
-                    orientation: (orientation.w, [orientation.x, orientation.y, orientation.z]),
-                    position: [position.x, position.y, position.z]
-                });
-            }
-            poses.into_iter()
+        self.poses.eye_poses()
+    }
+
+    /// Region of the given eye's texture that is rendered to for this frame. Starts out as the
+    /// binding's viewport.
+    pub fn viewport(&self, eye: &Eye) -> Viewport {
+        Viewport::from_native(&self.textures[eye_index(eye)].RenderViewport)
+    }
+
+    /// Render the given eye to a different region of its texture for this frame only, for
+    /// example to lower the resolution under heavy GPU load without reallocating textures. The
+    /// projection matrix stays the same: render the eye's full field of view into the viewport,
+    /// and distortion (including `uv_scale_offset`) accounts for the change.
+    ///
+    /// # Failure
+    ///
+    /// Fails with `Err(OculusError::InvalidInput)` if `viewport` doesn't lie within the eye's
+    /// texture.
+    pub fn set_viewport(&mut self, eye: &Eye, viewport: Viewport) -> Result<(), OculusError> {
+        let ref mut texture = self.textures[eye_index(eye)];
+        let size = texture.TextureSize;
+        if !viewport.fits((size.w as u32, size.h as u32)) {
+            return Err(OculusError::InvalidInput("viewport must lie within the eye's texture"));
         }
+        texture.RenderViewport = viewport.native();
+        Ok(())
+    }
+
+    /// Set both eyes' viewports to the binding's viewports scaled by `scale`, keeping their top
+    /// left corners in place. Scaling always starts from the binding, so `scale` is the fraction
+    /// of full resolution to render at, however many times this is called. A convenience for
+    /// dynamic resolution; see `set_viewport`.
+    ///
+    /// # Failure
+    ///
+    /// Fails with `Err(OculusError::InvalidInput)` if `scale` isn't greater than zero and at most
+    /// one.
+    pub fn scale_viewports(&mut self, scale: f32) -> Result<(), OculusError> {
+        if !(scale > 0f32 && scale <= 1f32) {
+            return Err(OculusError::InvalidInput("viewport scale must be in (0, 1]"));
+        }
+        for (texture, base) in self.textures.iter_mut().zip(self.binding.textures.iter()) {
+            let ref base_size = base.RenderViewport.Size;
+            texture.RenderViewport.Pos = base.RenderViewport.Pos;
+            texture.RenderViewport.Size = ffi::ovrSizei {
+                w: cmp::max(1, (base_size.w as f32 * scale) as i32),
+                h: cmp::max(1, (base_size.h as f32 * scale) as i32)
+            };
+        }
+        Ok(())
+    }
+
+    /// Scale and offset, as `(scale, offset)`, that map a distortion mesh's eye angle tangents to
+    /// texture coordinates in the given eye's viewport for this frame.
+    pub fn uv_scale_offset(&self, eye: &Eye) -> ([f32; 2], [f32; 2]) {
+        self.owning_context.eye_uv_scale_offset(eye, &self.textures)
+    }
+
+    /// Start and end timewarp rotation matrices for the given eye, in column-major format. Blend
+    /// between them using each distortion vertex's `timewarp_factor`. Call as late as possible,
+    /// immediately before the distortion pass.
+    pub fn timewarp_matrices(&self, eye: &Eye) -> (Matrix4, Matrix4) {
+        self.owning_context.eye_timewarp_matrices(eye, &self.poses.native_poses)
+    }
+
+    /// Time this frame began, in seconds on the SDK clock.
+    pub fn time(&self) -> f64 {
+        self.poses.time
+    }
+
+    /// Display timing for this frame, as predicted by the SDK. Unlike `time`, this always comes
+    /// from the headset, even while replaying recorded poses.
+    pub fn timing(&self) -> FrameTiming {
+        self.timing
+    }
+
+    /// Tracking state sampled along with this frame's eye poses, in the headset's current tracking
+    /// space.
+    pub fn tracking_state(&self) -> TrackingState {
+        self.poses.tracking_state
+    }
+
+    /// Tracking status for this frame's poses.
+    pub fn tracking_status(&self) -> TrackingStatus {
+        self.poses.tracking_state.status
+    }
+
+    /// Returns an event if positional tracking was lost or regained since the previous frame on
+    /// this `RenderContext`. Each change is reported on exactly one frame.
+    pub fn tracking_event(&self) -> Option<TrackingEvent> {
+        self.tracking_event
+    }
+
+    /// Finish the frame and hand it to the SDK: distort and present both eyes' textures, or with
+    /// client distortion, just record the frame's timing. Returns an error if the SDK reported an
+    /// error while ending the frame.
+    pub fn submit(mut self) -> Result<SubmitInfo, OculusError> {
+        let error = self.end(true);
+        self.owning_context.submit_info(&self.poses, error)
+    }
+
+    /// Finish the frame without submitting its textures. The frame's index is still used up.
+    ///
+    /// The SDK has to end every frame it began with a set of textures, so with SDK distortion
+    /// this presents a black frame in place of the eye textures, and none of this frame's partial
+    /// drawing is shown. With client distortion, only the frame's timing is recorded.
+    pub fn abort(mut self) {
+        self.end(false);
+    }
+
+    fn end(&mut self, submit: bool) -> Option<String> {
+        self.finished = true;
+        let context = self.owning_context;
+        let own = (self.poses.native_poses, self.textures);
+        let error = if submit {
+            context.end_native_frame(own.0, own.1)
+        } else {
+            context.abort_native_frame(own);
+            None
+        };
+        context.frame_open.store(false, atomic::Ordering::SeqCst);
+        error
     }
 }
 
 impl<'a> Drop for Frame<'a> {
     fn drop(&mut self) {
-        unsafe {
-            let invoker = self.owning_context.owning_hmd.context.invoker();
-            invoker.ovrHmd_EndFrame(self.owning_context.owning_hmd.native_hmd,
-                                    &self.poses,
-                                    &self.textures.textures);
+        if !self.finished {
+            let submit = !thread::panicking();
+            self.end(submit);
+        }
+    }
+}
+
+/// A frame begun with `RenderContext::begin_frame`. Unlike `Frame`, a token doesn't borrow its
+/// context, and it can be sent between threads while its context stays on the thread that created
+/// it. Call `RenderContext::start_render` before drawing the frame, then pass the token to
+/// `RenderContext::end_frame` to submit it, or `RenderContext::abort_frame` to discard it.
+///
+/// Dropping a token abandons its frame. If rendering had started, the SDK's frame is ended as
+/// `abort_frame` would when the next frame begins or the context is dropped.
+pub struct FrameToken {
+    // the owning context's open flag, which also identifies the context
+    frame_open: Arc<atomic::AtomicBool>,
+    textures: [ffi::ovrGLTexture; 2],
+    poses: FramePoses,
+    timing: FrameTiming,
+    tracking_event: Option<TrackingEvent>,
+    ended: bool
+}
+
+impl FrameToken {
+    /// Index of this frame on its `RenderContext`.
+    pub fn index(&self) -> u32 {
+        self.poses.frame_index
+    }
+
+    /// Get an iterable list of eye poses that should be drawn for this frame. These are returned
+    /// in the suggested rendering order.
+    pub fn eye_poses(&self) -> vec::IntoIter<FrameEyePose> {
+        self.poses.eye_poses()
+    }
+
+    /// Region of the given eye's texture that is rendered to for this frame.
+    pub fn viewport(&self, eye: &Eye) -> Viewport {
+        Viewport::from_native(&self.textures[eye_index(eye)].RenderViewport)
+    }
+
+    /// Time this frame began, in seconds on the SDK clock.
+    pub fn time(&self) -> f64 {
+        self.poses.time
+    }
+
+    /// Display timing predicted for this frame when it began.
+    pub fn timing(&self) -> FrameTiming {
+        self.timing
+    }
+
+    /// Tracking state sampled along with this frame's eye poses.
+    pub fn tracking_state(&self) -> TrackingState {
+        self.poses.tracking_state
+    }
+
+    /// Returns an event if positional tracking was lost or regained since the previous frame on
+    /// this frame's `RenderContext`.
+    pub fn tracking_event(&self) -> Option<TrackingEvent> {
+        self.tracking_event
+    }
+}
+
+impl Drop for FrameToken {
+    fn drop(&mut self) {
+        if !self.ended {
+            self.frame_open.store(false, atomic::Ordering::SeqCst);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Viewport;
+
+    #[test]
+    fn viewport_bounds() {
+        let viewport = |x: i32, y: i32, width: u32, height: u32| Viewport {
+            x: x,
+            y: y,
+            width: width,
+            height: height
+        };
+        assert!(viewport(0, 0, 100, 50).fits((100, 50)));
+        assert!(viewport(50, 10, 50, 40).fits((100, 50)));
+        assert!(!viewport(51, 0, 50, 50).fits((100, 50)));
+        assert!(!viewport(-1, 0, 10, 10).fits((100, 50)));
+
+        // sums that would overflow i32 or u32 are out of bounds, not wrapped
+        assert!(!viewport(i32::max_value(), 0, u32::max_value(), 1).fits((100, 50)));
+        assert!(!viewport(0, 0, 1, 1).fits((u32::max_value(), 1)));
+    }
+}
@@ -0,0 +1,624 @@
+//! Software reference implementation of the distortion pass.
+//!
+//! `distort` combines a pair of eye images into a final, lens-corrected headset frame entirely on
+//! the CPU, with no GPU required. It's intended for golden-image tests of stereo output: render or
+//! read back the eye images, distort them, and compare against a stored image.
+//!
+//! Distortion is described by a `DistortionMesh` and a texture scale and offset for each eye. The
+//! meshes are rasterized the way the client distortion shader described in the `render` module
+//! draws them, without timewarp. They can come from two places:
+//!
+//! * Generated in Rust from `LensParameters`, with `distortion_mesh` and `uv_scale_offset`, or all
+//!   at once with `distort_with_lenses`. This needs neither the Oculus runtime nor a headset, so
+//!   it runs anywhere tests do.
+//! * From the SDK, with `RenderContext::distortion_mesh` and `Frame::uv_scale_offset`, to check
+//!   output against the runtime's own lens model. These can be recorded once from a headset and
+//!   stored alongside the golden images.
+//!
+//! Texture coordinates `(0, 0)` address the first pixel of an eye image's `data`, with `v`
+//! increasing row by row. Flip images vertically if they were read back in the opposite order to
+//! the one the texture coordinates were computed for.
+//!
+//! # Example
+//!
+//! A lens without distortion maps each eye's field of view straight onto its half of the display,
+//! leaving the eye images unchanged:
+//!
+//! ```
+//! use rovr::render::{FovPort, Viewport};
+//! use rovr::software::{self, Image, LensParameters};
+//!
+//! let mut eye = Image::new(4, 4);
+//! eye.set_pixel(1, 2, [255, 128, 0, 255]);
+//!
+//! let fov = FovPort { up_tan: 1f32, down_tan: 1f32, left_tan: 1f32, right_tan: 1f32 };
+//! let lens = LensParameters::undistorted(&fov);
+//! let viewport = Viewport { x: 0, y: 0, width: 4, height: 4 };
+//! let frame = software::distort_with_lenses([&eye, &eye],
+//!                                           [&viewport, &viewport],
+//!                                           [&fov, &fov],
+//!                                           [&lens, &lens],
+//!                                           8, 4).unwrap();
+//! assert_eq!(frame.pixel(1, 2), [255, 128, 0, 255]);
+//! assert_eq!(frame.pixel(5, 2), [255, 128, 0, 255]);
+//! assert_eq!(frame.pixel(0, 0), [0, 0, 0, 255]);
+//! ```
+
+use Eye;
+use OculusError;
+use shim::{DistortionMesh, DistortionVertex, FovPort, Viewport};
+
+/// Number of quads along each side of the meshes `distort_with_lenses` generates.
+const MESH_RESOLUTION: u8 = 64;
+
+/// Number of bytes needed for a `width` by `height` RGBA image, or `None` if it doesn't fit in
+/// memory.
+fn data_len(width: u32, height: u32) -> Option<usize> {
+    (width as usize).checked_mul(height as usize).and_then(|pixels| pixels.checked_mul(4))
+}
+
+/// An 8-bit RGBA image, stored row by row from the top left.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>
+}
+
+impl Image {
+    /// Create an opaque black image.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is too large to address.
+    pub fn new(width: u32, height: u32) -> Image {
+        let len = data_len(width, height).expect("image is too large");
+        let mut data = vec![0u8; len];
+        for pixel in data.chunks_mut(4) {
+            pixel[3] = 255;
+        }
+        Image {
+            width: width,
+            height: height,
+            data: data
+        }
+    }
+
+    /// RGBA value of the pixel at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is outside the image.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = self.index(x, y);
+        [self.data[i], self.data[i + 1], self.data[i + 2], self.data[i + 3]]
+    }
+
+    /// Set the RGBA value of the pixel at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is outside the image.
+    pub fn set_pixel(&mut self, x: u32, y: u32, value: [u8; 4]) {
+        let i = self.index(x, y);
+        for c in 0..4 {
+            self.data[i + c] = value[c];
+        }
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        assert!(x < self.width && y < self.height, "pixel out of bounds");
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+
+    /// True if `data` holds exactly `width` by `height` pixels.
+    fn is_valid(&self) -> bool {
+        data_len(self.width, self.height) == Some(self.data.len())
+    }
+}
+
+/// Optical parameters of one eye's lens, for generating a distortion mesh without the SDK.
+///
+/// Points on an eye's half of the display are measured in that half's normalized device
+/// coordinates, from -1 to 1 with y pointing up. Without a lens, a point at offset `d` from
+/// `center` would be seen at eye angle tangents `(d.x * tan_scale[0], -d.y * tan_scale[1])`, with
+/// y tangents increasing downwards. The lens scales those tangents by `k[0] + k[1] r² + k[2] r⁴ +
+/// k[3] r⁶`, where `r` is their length. Red is scaled further by `1 + chromatic_aberration[0] +
+/// chromatic_aberration[1] r²`, and blue by `1 + chromatic_aberration[2] + chromatic_aberration[3]
+/// r²`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LensParameters {
+    /// Point on the eye's half of the display that lies on the lens axis.
+    pub center: [f32; 2],
+
+    /// Eye angle tangents per unit of display, horizontally and vertically, before distortion.
+    pub tan_scale: [f32; 2],
+
+    /// Radial distortion coefficients.
+    pub k: [f32; 4],
+
+    /// Chromatic aberration coefficients for red, then blue.
+    pub chromatic_aberration: [f32; 4],
+
+    /// Width of the fade to black at the edges of the eye's half of the display, in the same
+    /// units as `center`. Zero disables the vignette.
+    pub vignette_width: f32
+}
+
+impl LensParameters {
+    /// A lens that maps `fov` exactly onto the eye's half of the display, with no distortion,
+    /// chromatic aberration or vignette. A starting point for modelling a real lens, and enough
+    /// on its own for tests that only check stereo layout.
+    pub fn undistorted(fov: &FovPort) -> LensParameters {
+        let horizontal = fov.left_tan + fov.right_tan;
+        let vertical = fov.up_tan + fov.down_tan;
+        LensParameters {
+            center: [(fov.left_tan - fov.right_tan) / horizontal,
+                     (fov.down_tan - fov.up_tan) / vertical],
+            tan_scale: [horizontal * 0.5f32, vertical * 0.5f32],
+            k: [1f32, 0f32, 0f32, 0f32],
+            chromatic_aberration: [0f32; 4],
+            vignette_width: 0f32
+        }
+    }
+
+    /// Distorted eye angle tangents for red, green and blue at a point on the eye's half of the
+    /// display.
+    fn tan_eye_angles(&self, point: &[f32; 2]) -> [[f32; 2]; 3] {
+        let tan = [(point[0] - self.center[0]) * self.tan_scale[0],
+                   (self.center[1] - point[1]) * self.tan_scale[1]];
+        let r2 = tan[0] * tan[0] + tan[1] * tan[1];
+        let ref k = self.k;
+        let scale = k[0] + r2 * (k[1] + r2 * (k[2] + r2 * k[3]));
+        let ref ca = self.chromatic_aberration;
+        let scaled = |channel_scale: f32| {
+            [tan[0] * scale * channel_scale, tan[1] * scale * channel_scale]
+        };
+        [scaled(1f32 + ca[0] + ca[1] * r2), scaled(1f32), scaled(1f32 + ca[2] + ca[3] * r2)]
+    }
+}
+
+/// Generate a distortion mesh for the given eye from its lens parameters, as a grid of
+/// `resolution` by `resolution` quads covering the eye's half of the display. Use it with the
+/// eye's `uv_scale_offset`.
+///
+/// # Failure
+///
+/// Fails with `Err(OculusError::InvalidInput)` if `resolution` is zero.
+pub fn distortion_mesh(eye: &Eye,
+                       lens: &LensParameters,
+                       resolution: u8) -> Result<DistortionMesh, OculusError> {
+    if resolution == 0 {
+        return Err(OculusError::InvalidInput("distortion mesh resolution must be at least 1"));
+    }
+    // the eye's half of the display, as an offset into the whole display's NDC
+    let screen_offset = match eye {
+        &Eye::Left => -1f32,
+        &Eye::Right => 0f32
+    };
+
+    let n = resolution as u16;
+    let mut vertices = Vec::with_capacity((n as usize + 1) * (n as usize + 1));
+    for row in 0..(n + 1) {
+        for column in 0..(n + 1) {
+            let point = [column as f32 / n as f32 * 2f32 - 1f32,
+                         1f32 - row as f32 / n as f32 * 2f32];
+            let vignette = if lens.vignette_width > 0f32 {
+                let edge = (1f32 - point[0].abs()).min(1f32 - point[1].abs());
+                (edge / lens.vignette_width).max(0f32).min(1f32)
+            } else {
+                1f32
+            };
+            let tans = lens.tan_eye_angles(&point);
+            vertices.push(DistortionVertex {
+                screen_position: [screen_offset + (point[0] + 1f32) * 0.5f32, point[1]],
+                timewarp_factor: 0f32,
+                vignette_factor: vignette,
+                tan_eye_angles_r: tans[0],
+                tan_eye_angles_g: tans[1],
+                tan_eye_angles_b: tans[2]
+            });
+        }
+    }
+
+    let mut indices = Vec::with_capacity(n as usize * n as usize * 6);
+    for row in 0..n {
+        for column in 0..n {
+            let top_left = row * (n + 1) + column;
+            let bottom_left = top_left + n + 1;
+            indices.extend([top_left, top_left + 1, bottom_left,
+                            bottom_left, top_left + 1, bottom_left + 1].iter().cloned());
+        }
+    }
+    Ok(DistortionMesh {
+        vertices: vertices,
+        indices: indices
+    })
+}
+
+/// Scale and offset, as `(scale, offset)`, that map eye angle tangents within `fov` to texture
+/// coordinates within `viewport` of an image `image_size` pixels in size. The counterpart of
+/// `Frame::uv_scale_offset` for images that weren't rendered through the SDK, with `v`
+/// increasing row by row from the top of the image.
+pub fn uv_scale_offset(fov: &FovPort,
+                       viewport: &Viewport,
+                       image_size: (u32, u32)) -> ([f32; 2], [f32; 2]) {
+    let (width, height) = (image_size.0 as f32, image_size.1 as f32);
+    let horizontal = fov.left_tan + fov.right_tan;
+    let vertical = fov.up_tan + fov.down_tan;
+    let scale = [viewport.width as f32 / (horizontal * width),
+                 viewport.height as f32 / (vertical * height)];
+    let offset = [(viewport.x as f32 + fov.left_tan * viewport.width as f32 / horizontal) / width,
+                  (viewport.y as f32 + fov.up_tan * viewport.height as f32 / vertical) / height];
+    (scale, offset)
+}
+
+/// Produce a distorted `width` by `height` headset frame from a pair of eye images, using
+/// distortion meshes generated from each eye's lens parameters. Each eye is sampled from its
+/// `viewport` of its image, which was rendered with its `fov`; for images rendered to a
+/// `TextureBinding`, pass its `viewport` for each eye. Arrays are indexed left eye first. Needs
+/// neither the Oculus runtime nor a headset.
+///
+/// # Failure
+///
+/// Fails with `Err(OculusError::InvalidInput)` if a field of view doesn't span a positive angle
+/// both horizontally and vertically, or for any of the reasons `distort` fails.
+pub fn distort_with_lenses(eye_images: [&Image; 2],
+                           viewports: [&Viewport; 2],
+                           fovs: [&FovPort; 2],
+                           lenses: [&LensParameters; 2],
+                           width: u32,
+                           height: u32) -> Result<Image, OculusError> {
+    for fov in fovs.iter() {
+        if !(fov.left_tan + fov.right_tan > 0f32 && fov.up_tan + fov.down_tan > 0f32) {
+            return Err(OculusError::InvalidInput("field of view must span a positive angle"));
+        }
+    }
+    let meshes = [try!(distortion_mesh(&Eye::Left, lenses[0], MESH_RESOLUTION)),
+                  try!(distortion_mesh(&Eye::Right, lenses[1], MESH_RESOLUTION))];
+    let image_size = |image: &Image| (image.width, image.height);
+    let uv_scale_offsets = [uv_scale_offset(fovs[0], viewports[0], image_size(eye_images[0])),
+                            uv_scale_offset(fovs[1], viewports[1], image_size(eye_images[1]))];
+    distort(eye_images, [&meshes[0], &meshes[1]], uv_scale_offsets, width, height)
+}
+
+/// Produce a distorted `width` by `height` headset frame from a pair of eye images, by
+/// rasterizing each eye's distortion mesh. Arrays are indexed left eye first; both eyes may share
+/// a single image. `uv_scale_offsets` map the meshes' eye angle tangents to texture coordinates
+/// in each image, as returned by `Frame::uv_scale_offset` or `uv_scale_offset`.
+///
+/// Each colour channel samples its image bilinearly at its own tangents, then is multiplied by the
+/// vertex's `vignette_factor`. Samples outside an image are black, as is any part of the frame
+/// that neither mesh covers.
+///
+/// # Failure
+///
+/// Fails with `Err(OculusError::InvalidInput)` if an image's data doesn't match its size, a mesh
+/// index is out of range, or the frame is too large to address.
+///
+/// ```
+/// use rovr::render::{DistortionMesh, DistortionVertex};
+/// use rovr::software::{self, Image};
+///
+/// let mut eye = Image::new(16, 16);
+/// for y in 0..16 {
+///     for x in 0..16 {
+///         eye.set_pixel(x, y, [255, 255, 255, 255]);
+///     }
+/// }
+///
+/// // a single triangle in the left half, where red samples further out than green and blue and
+/// // falls off the image near the outer corner
+/// let vertex = |x: f32, y: f32, tan: [f32; 2]| DistortionVertex {
+///     screen_position: [x, y],
+///     vignette_factor: 1f32,
+///     tan_eye_angles_r: [tan[0] * 1.5f32, tan[1] * 1.5f32],
+///     tan_eye_angles_g: tan,
+///     tan_eye_angles_b: tan,
+///     .. Default::default()
+/// };
+/// let mesh = DistortionMesh {
+///     vertices: vec![vertex(-1f32, 1f32, [-1f32, -1f32]),
+///                    vertex(0f32, 1f32, [1f32, -1f32]),
+///                    vertex(-1f32, -1f32, [-1f32, 1f32])],
+///     indices: vec![0, 1, 2]
+/// };
+/// let empty = DistortionMesh::default();
+/// let uv_scale_offset = ([0.5f32, 0.5f32], [0.5f32, 0.5f32]);
+/// let frame = software::distort([&eye, &eye],
+///                               [&mesh, &empty],
+///                               [uv_scale_offset, uv_scale_offset],
+///                               32, 16).unwrap();
+///
+/// assert_eq!(frame.pixel(4, 4), [255, 255, 255, 255]);
+/// assert_eq!(frame.pixel(0, 0), [0, 255, 255, 255]);
+/// assert_eq!(frame.pixel(24, 8), [0, 0, 0, 255]);
+/// ```
+pub fn distort(eye_images: [&Image; 2],
+               meshes: [&DistortionMesh; 2],
+               uv_scale_offsets: [([f32; 2], [f32; 2]); 2],
+               width: u32,
+               height: u32) -> Result<Image, OculusError> {
+    if !eye_images[0].is_valid() || !eye_images[1].is_valid() {
+        return Err(OculusError::InvalidInput("eye image data doesn't match its size"));
+    }
+    for mesh in meshes.iter() {
+        if mesh.indices.iter().any(|&i| i as usize >= mesh.vertices.len()) {
+            return Err(OculusError::InvalidInput("distortion mesh index out of range"));
+        }
+    }
+    if data_len(width, height).is_none() {
+        return Err(OculusError::InvalidInput("distorted frame is too large"));
+    }
+
+    let mut output = Image::new(width, height);
+    for eye in 0..2 {
+        let ref mesh = meshes[eye];
+        for triangle in mesh.indices.chunks(3).filter(|triangle| triangle.len() == 3) {
+            let vertices = [&mesh.vertices[triangle[0] as usize],
+                            &mesh.vertices[triangle[1] as usize],
+                            &mesh.vertices[triangle[2] as usize]];
+            rasterize(&mut output, eye_images[eye], &uv_scale_offsets[eye], &vertices);
+        }
+    }
+    Ok(output)
+}
+
+/// Draw one triangle of a distortion mesh into `output`, covering the pixels whose centers lie
+/// inside it.
+fn rasterize(output: &mut Image,
+             image: &Image,
+             uv_scale_offset: &([f32; 2], [f32; 2]),
+             vertices: &[&DistortionVertex; 3]) {
+    // screen positions are in NDC with y pointing up
+    let (w, h) = (output.width as f32, output.height as f32);
+    let points: Vec<[f32; 2]> = vertices.iter().map(|v| {
+        [(v.screen_position[0] + 1f32) * 0.5f32 * w, (1f32 - v.screen_position[1]) * 0.5f32 * h]
+    }).collect();
+    let edge = |a: &[f32; 2], b: &[f32; 2], p: &[f32; 2]| {
+        (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+    };
+    let area = edge(&points[0], &points[1], &points[2]);
+    if area == 0f32 || !area.is_finite() {
+        return;
+    }
+
+    // clip the triangle's bounds to the frame
+    let min_x = points.iter().fold(w, |m, p| m.min(p[0])).max(0f32).floor() as u32;
+    let max_x = points.iter().fold(0f32, |m, p| m.max(p[0])).min(w).ceil() as u32;
+    let min_y = points.iter().fold(h, |m, p| m.min(p[1])).max(0f32).floor() as u32;
+    let max_y = points.iter().fold(0f32, |m, p| m.max(p[1])).min(h).ceil() as u32;
+
+    let (ref scale, ref offset) = *uv_scale_offset;
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let p = [x as f32 + 0.5f32, y as f32 + 0.5f32];
+            let weights = [edge(&points[1], &points[2], &p) / area,
+                           edge(&points[2], &points[0], &p) / area,
+                           edge(&points[0], &points[1], &p) / area];
+            if weights.iter().any(|&weight| weight < 0f32) {
+                continue;
+            }
+            let interpolate = |attribute: &Fn(&DistortionVertex) -> f32| {
+                weights[0] * attribute(vertices[0]) +
+                    weights[1] * attribute(vertices[1]) +
+                    weights[2] * attribute(vertices[2])
+            };
+
+            let vignette = interpolate(&|v| v.vignette_factor).max(0f32).min(1f32);
+            let tans = [[interpolate(&|v| v.tan_eye_angles_r[0]),
+                         interpolate(&|v| v.tan_eye_angles_r[1])],
+                        [interpolate(&|v| v.tan_eye_angles_g[0]),
+                         interpolate(&|v| v.tan_eye_angles_g[1])],
+                        [interpolate(&|v| v.tan_eye_angles_b[0]),
+                         interpolate(&|v| v.tan_eye_angles_b[1])]];
+            let mut value = [0u8, 0u8, 0u8, 255u8];
+            for channel in 0..3 {
+                let ref tan = tans[channel];
+                let uv = [tan[0] * scale[0] + offset[0], tan[1] * scale[1] + offset[1]];
+                if let Some(sample) = sample(image, &uv) {
+                    value[channel] = (sample[channel] as f32 * vignette).round() as u8;
+                }
+            }
+            output.set_pixel(x, y, value);
+        }
+    }
+}
+
+/// Bilinearly sample `image` at texture coordinates `uv`, or `None` if they're outside it.
+fn sample(image: &Image, uv: &[f32; 2]) -> Option<[u8; 4]> {
+    if !(uv[0] >= 0f32 && uv[0] <= 1f32 && uv[1] >= 0f32 && uv[1] <= 1f32) ||
+            image.width == 0 || image.height == 0 {
+        return None;
+    }
+
+    let max = [(image.width - 1) as f32, (image.height - 1) as f32];
+    let sx = (uv[0] * image.width as f32 - 0.5f32).max(0f32).min(max[0]);
+    let sy = (uv[1] * image.height as f32 - 0.5f32).max(0f32).min(max[1]);
+    let (x0, y0) = (sx.floor(), sy.floor());
+    let (fx, fy) = (sx - x0, sy - y0);
+    let (x0, y0) = (x0 as u32, y0 as u32);
+    let x1 = if fx > 0f32 { x0 + 1 } else { x0 };
+    let y1 = if fy > 0f32 { y0 + 1 } else { y0 };
+
+    let (p00, p10) = (image.pixel(x0, y0), image.pixel(x1, y0));
+    let (p01, p11) = (image.pixel(x0, y1), image.pixel(x1, y1));
+    let mut result = [0u8; 4];
+    for c in 0..4 {
+        let top = p00[c] as f32 + (p10[c] as f32 - p00[c] as f32) * fx;
+        let bottom = p01[c] as f32 + (p11[c] as f32 - p01[c] as f32) * fx;
+        result[c] = (top + (bottom - top) * fy).round() as u8;
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use Eye;
+    use OculusError;
+    use shim::{DistortionMesh, DistortionVertex, FovPort, Viewport};
+    use super::{distort, distort_with_lenses, distortion_mesh, uv_scale_offset};
+    use super::{Image, LensParameters};
+
+    const FULL_TEXTURE: ([f32; 2], [f32; 2]) = ([0.5f32, 0.5f32], [0.5f32, 0.5f32]);
+
+    /// A quad mapping tan angles of -1 to 1 onto the display between NDC x = `left` and
+    /// `right`.
+    fn quad(left: f32, right: f32, vignette: f32) -> DistortionMesh {
+        let corner = |x: f32, y: f32| {
+            let tan = [x * 2f32 - 1f32, 1f32 - y * 2f32];
+            DistortionVertex {
+                screen_position: [left + x * (right - left), y * 2f32 - 1f32],
+                vignette_factor: vignette,
+                tan_eye_angles_r: tan,
+                tan_eye_angles_g: tan,
+                tan_eye_angles_b: tan,
+                .. Default::default()
+            }
+        };
+        DistortionMesh {
+            vertices: vec![corner(0f32, 0f32), corner(1f32, 0f32),
+                           corner(0f32, 1f32), corner(1f32, 1f32)],
+            indices: vec![0, 1, 2, 2, 1, 3]
+        }
+    }
+
+    fn filled(width: u32, height: u32, value: [u8; 4]) -> Image {
+        let mut image = Image::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                image.set_pixel(x, y, value);
+            }
+        }
+        image
+    }
+
+    #[test]
+    fn odd_widths_are_covered() {
+        let eye = filled(4, 4, [255, 255, 255, 255]);
+        let (left, right) = (quad(-1f32, 0f32, 1f32), quad(0f32, 1f32, 1f32));
+        let frame = distort([&eye, &eye], [&left, &right], [FULL_TEXTURE, FULL_TEXTURE], 9, 3)
+            .unwrap();
+        for y in 0..3 {
+            for x in 0..9 {
+                assert_eq!(frame.pixel(x, y), [255, 255, 255, 255], "pixel ({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn vignette() {
+        let eye = filled(4, 4, [200, 100, 50, 255]);
+        let mesh = quad(-1f32, 1f32, 0.5f32);
+        let empty = DistortionMesh::default();
+        let frame = distort([&eye, &eye], [&mesh, &empty], [FULL_TEXTURE, FULL_TEXTURE], 4, 4)
+            .unwrap();
+        assert_eq!(frame.pixel(2, 2), [100, 50, 25, 255]);
+    }
+
+    #[test]
+    fn samples_outside_the_image() {
+        // texture coordinates that run off every edge of the image, and a mesh that extends past
+        // the frame, are clipped rather than panicking
+        let eye = filled(4, 4, [255, 255, 255, 255]);
+        let mesh = quad(-2f32, 2f32, 1f32);
+        let scale_offset = ([2f32, 2f32], [0.5f32, 0.5f32]);
+        let frame = distort([&eye, &eye], [&mesh, &mesh], [scale_offset, scale_offset], 8, 8)
+            .unwrap();
+        assert_eq!(frame.pixel(4, 4), [255, 255, 255, 255]);
+        assert_eq!(frame.pixel(0, 0), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let eye = Image::new(4, 4);
+        let mut mesh = quad(-1f32, 0f32, 1f32);
+        mesh.indices.push(7);
+        match distort([&eye, &eye], [&mesh, &mesh], [FULL_TEXTURE, FULL_TEXTURE], 8, 4) {
+            Err(OculusError::InvalidInput(_)) => (),
+            other => panic!("unexpected result {:?}", other)
+        }
+
+        let truncated = Image { data: vec![0u8; 12], .. Image::new(4, 4) };
+        let mesh = quad(-1f32, 0f32, 1f32);
+        match distort([&truncated, &eye], [&mesh, &mesh], [FULL_TEXTURE, FULL_TEXTURE], 8, 4) {
+            Err(OculusError::InvalidInput(_)) => (),
+            other => panic!("unexpected result {:?}", other)
+        }
+    }
+
+    #[test]
+    fn uv_scale_offset_within_viewport() {
+        // the right half of a shared texture, rendered with an asymmetric field of view
+        let fov = FovPort { up_tan: 1f32, down_tan: 3f32, left_tan: 1f32, right_tan: 1f32 };
+        let viewport = Viewport { x: 100, y: 0, width: 100, height: 50 };
+        let (scale, offset) = uv_scale_offset(&fov, &viewport, (200, 100));
+        let uv = |tan: [f32; 2]| [tan[0] * scale[0] + offset[0], tan[1] * scale[1] + offset[1]];
+        assert_eq!(uv([-1f32, -1f32]), [0.5f32, 0f32]);
+        assert_eq!(uv([1f32, 3f32]), [1f32, 0.5f32]);
+        assert_eq!(uv([0f32, 0f32]), [0.75f32, 0.125f32]);
+    }
+
+    #[test]
+    fn generated_mesh() {
+        let fov = FovPort { up_tan: 1f32, down_tan: 1f32, left_tan: 1f32, right_tan: 1f32 };
+        let lens = LensParameters {
+            k: [1f32, 0.25f32, 0f32, 0f32],
+            chromatic_aberration: [0.1f32, 0f32, -0.1f32, 0f32],
+            vignette_width: 0.5f32,
+            .. LensParameters::undistorted(&fov)
+        };
+        let mesh = distortion_mesh(&Eye::Right, &lens, 2).unwrap();
+        assert_eq!(mesh.vertices.len(), 9);
+        assert_eq!(mesh.indices.len(), 24);
+
+        // the grid's middle vertex lies on the lens axis, in the middle of the right half
+        let ref center = mesh.vertices[4];
+        assert_eq!(center.screen_position, [0.5f32, 0f32]);
+        assert_eq!(center.tan_eye_angles_g, [0f32, 0f32]);
+        assert_eq!(center.vignette_factor, 1f32);
+
+        // the top left corner is pushed outwards, red further than green and blue less far
+        let ref corner = mesh.vertices[0];
+        assert_eq!(corner.screen_position, [0f32, 1f32]);
+        assert_eq!(corner.tan_eye_angles_g, [-1.5f32, -1.5f32]);
+        assert!(corner.tan_eye_angles_r[0] < -1.5f32 && corner.tan_eye_angles_b[0] > -1.5f32);
+        assert_eq!(corner.vignette_factor, 0f32);
+
+        assert!(distortion_mesh(&Eye::Left, &lens, 0).is_err());
+    }
+
+    #[test]
+    fn barrel_distortion_magnifies_the_center() {
+        // a lens that samples nearer the middle of the eye image shows it enlarged, pushing a one
+        // pixel border off the visible area
+        let mut eye = filled(16, 16, [255, 255, 255, 255]);
+        for i in 0..16 {
+            eye.set_pixel(i, 0, [255, 0, 0, 255]);
+            eye.set_pixel(0, i, [255, 0, 0, 255]);
+        }
+        let fov = FovPort { up_tan: 1f32, down_tan: 1f32, left_tan: 1f32, right_tan: 1f32 };
+        let undistorted = LensParameters::undistorted(&fov);
+        let barrel = LensParameters { k: [0.8f32, 0f32, 0f32, 0f32], .. undistorted };
+        let viewport = Viewport { x: 0, y: 0, width: 16, height: 16 };
+
+        let plain = distort_with_lenses([&eye, &eye], [&viewport, &viewport], [&fov, &fov],
+                                        [&undistorted, &undistorted], 32, 16).unwrap();
+        assert_eq!(plain.pixel(0, 0), [255, 0, 0, 255]);
+        let distorted = distort_with_lenses([&eye, &eye], [&viewport, &viewport], [&fov, &fov],
+                                            [&barrel, &barrel], 32, 16).unwrap();
+        assert_eq!(distorted.pixel(0, 0), [255, 255, 255, 255]);
+        assert_eq!(distorted.pixel(16, 8), [255, 255, 255, 255]);
+
+        let empty = FovPort::default();
+        assert!(distort_with_lenses([&eye, &eye], [&viewport, &viewport], [&empty, &fov],
+                                    [&barrel, &barrel], 32, 16).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "image is too large")]
+    fn oversized_image() {
+        Image::new(u32::max_value(), u32::max_value());
+    }
+}
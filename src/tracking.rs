@@ -0,0 +1,179 @@
+//! Types describing headset tracking state, independent of rendering.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # extern crate rovr;
+//! # use rovr::{Context, TrackingOptions};
+//! # fn main() {
+//! let hmd = Context::new().unwrap()
+//!     .build_hmd()
+//!     .track(&TrackingOptions::with_all())
+//!     .build().unwrap();
+//!
+//! let state = hmd.tracking_state(0.0);
+//! if state.status.position_tracked {
+//!     let ref p = state.head.pose.position;
+//!     println!("head at {}, {}, {} moving at {:?}", p[0], p[1], p[2], state.head.linear_velocity);
+//! }
+//! # }
+//! ```
+
+pub use shim::Pose;
+pub use shim::PoseState;
+pub use shim::TrackingStatus;
+pub use shim::TrackingEvent;
+pub use shim::TrackingMonitor;
+pub use shim::TrackingState;
+pub use shim::PredictedPose;
+pub use shim::SensorData;
+pub use shim::SensorSamples;
+
+use math;
+use shim::{FrameEyePose, Quaternion};
+
+/// A reference frame for tracked poses. The Oculus SDK reports poses relative to an origin at eye
+/// level, positioned and facing wherever the headset was when tracking started or was last
+/// recentered. A `TrackingSpace` re-expresses those poses relative to an origin of the
+/// application's choosing.
+///
+/// Poses are transformed in three steps: first the yaw correction from `recenter_yaw` is applied,
+/// then the origin is lowered to the floor if `floor_level` is set, and finally the
+/// application-defined `offset` is applied.
+///
+/// ```
+/// use rovr::tracking::{Pose, TrackingSpace};
+///
+/// let mut space = TrackingSpace::new();
+/// space.floor_level(1.6f32);
+///
+/// let pose = space.transform_pose(&Pose::default());
+/// assert_eq!(pose.position, [0f32, 1.6f32, 0f32]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrackingSpace {
+    floor_height: Option<f32>,
+    yaw_correction: Quaternion,
+    offset: Pose
+}
+
+impl Default for TrackingSpace {
+    fn default() -> TrackingSpace {
+        TrackingSpace::new()
+    }
+}
+
+impl TrackingSpace {
+    /// The SDK's native tracking space: origin at eye level, with no corrections or offset.
+    pub fn new() -> TrackingSpace {
+        TrackingSpace {
+            floor_height: None,
+            yaw_correction: math::IDENTITY,
+            offset: Pose {
+                orientation: math::IDENTITY,
+                position: [0f32; 3]
+            }
+        }
+    }
+
+    /// Place the origin at eye level, where the SDK puts it.
+    pub fn eye_level<'f>(&'f mut self) -> &'f mut TrackingSpace {
+        self.floor_height = None;
+        self
+    }
+
+    /// Place the origin on the floor, `eye_height` meters below eye level. Use
+    /// `Hmd::profile_eye_height` for the height configured in the user's Oculus profile.
+    pub fn floor_level<'f>(&'f mut self, eye_height: f32) -> &'f mut TrackingSpace {
+        self.floor_height = Some(eye_height);
+        self
+    }
+
+    /// Rotate the space about the vertical axis so that `head`, a pose in the SDK's native space,
+    /// faces forward. Unlike `Hmd::recenter_pose`, this leaves position, pitch and roll alone.
+    pub fn recenter_yaw<'f>(&'f mut self, head: &Pose) -> &'f mut TrackingSpace {
+        self.yaw_correction = math::quat_from_yaw(-math::quat_yaw(&head.orientation));
+        self
+    }
+
+    /// Remove any yaw correction applied by `recenter_yaw`. `Hmd::recenter_pose` does this
+    /// automatically.
+    ///
+    /// ```
+    /// use rovr::tracking::{Pose, TrackingSpace};
+    ///
+    /// // head turned 90 degrees to the left
+    /// let half = 0.5f32.sqrt();
+    /// let head = Pose { orientation: (half, [0f32, half, 0f32]), position: [0f32; 3] };
+    ///
+    /// let mut space = TrackingSpace::new();
+    /// space.recenter_yaw(&head);
+    /// let (w, _) = space.transform_pose(&head).orientation;
+    /// assert!((w.abs() - 1f32).abs() < 1e-5f32);
+    ///
+    /// space.clear_yaw();
+    /// assert_eq!(space, TrackingSpace::new());
+    /// ```
+    pub fn clear_yaw<'f>(&'f mut self) -> &'f mut TrackingSpace {
+        self.yaw_correction = math::IDENTITY;
+        self
+    }
+
+    /// Apply an application-defined offset, such as the pose of a vehicle the user is sitting in.
+    /// Poses are reported as if the corrected tracking space were placed at `offset`.
+    pub fn offset<'f>(&'f mut self, offset: Pose) -> &'f mut TrackingSpace {
+        self.offset = offset;
+        self
+    }
+
+    fn rotation(&self) -> Quaternion {
+        math::quat_mul(&self.offset.orientation, &self.yaw_correction)
+    }
+
+    /// Transform a pose from the SDK's native tracking space into this space.
+    pub fn transform_pose(&self, pose: &Pose) -> Pose {
+        let mut position = math::quat_rotate(&self.yaw_correction, &pose.position);
+        if let Some(h) = self.floor_height {
+            position[1] += h;
+        }
+        Pose {
+            orientation: math::quat_mul(&self.rotation(), &pose.orientation),
+            position: math::add(&self.offset.position,
+                                &math::quat_rotate(&self.offset.orientation, &position))
+        }
+    }
+
+    /// Transform a frame eye pose from the SDK's native tracking space into this space.
+    pub fn transform_eye_pose(&self, pose: &FrameEyePose) -> FrameEyePose {
+        let transformed = self.transform_pose(&Pose {
+            orientation: pose.orientation,
+            position: pose.position
+        });
+        FrameEyePose {
+            orientation: transformed.orientation,
+            position: transformed.position,
+            .. *pose
+        }
+    }
+
+    /// Transform a tracking state from the SDK's native tracking space into this space. Poses
+    /// and their derivatives are transformed; raw sensor data is left in the headset's frame.
+    pub fn transform_state(&self, state: &TrackingState) -> TrackingState {
+        let rotation = self.rotation();
+        let ref head = state.head;
+        TrackingState {
+            head: PoseState {
+                pose: self.transform_pose(&head.pose),
+                angular_velocity: math::quat_rotate(&rotation, &head.angular_velocity),
+                linear_velocity: math::quat_rotate(&rotation, &head.linear_velocity),
+                angular_acceleration: math::quat_rotate(&rotation, &head.angular_acceleration),
+                linear_acceleration: math::quat_rotate(&rotation, &head.linear_acceleration),
+                time: head.time
+            },
+            camera_pose: self.transform_pose(&state.camera_pose),
+            leveled_camera_pose: self.transform_pose(&state.leveled_camera_pose),
+            .. *state
+        }
+    }
+}